@@ -0,0 +1,332 @@
+//! `spirv-cross-rs` cross-compiles SPIR-V modules to GLSL, HLSL or MSL and reflects their
+//! interfaces as JSON.
+
+mod options;
+mod reflect;
+
+use clap::{Parser, ValueEnum};
+use spirv_cross::spirv::{self, Ast, Compile, Parse};
+use spirv_cross::{glsl, hlsl, msl, ErrorCode};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+/// Cross-compiles a SPIR-V module to GLSL, HLSL or MSL, or reflects its interface as JSON.
+///
+/// Options which are not given keep the defaults of the `spirv_cross` crate. Boolean options
+/// may be given without a value to enable them, or as `--option=false` to disable them.
+#[derive(Debug, Parser)]
+#[command(name = "spirv-cross-rs", version)]
+struct Args {
+    /// The SPIR-V module to read, or `-` to read it from stdin.
+    input: PathBuf,
+
+    /// The language to compile to. Required unless `--reflect` is given.
+    #[arg(long, value_enum, required_unless_present = "reflect")]
+    target: Option<Target>,
+
+    /// Writes the output to a file instead of stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Writes the entry points, resources and specialization constants of the module as JSON
+    /// instead of compiling it.
+    #[arg(long)]
+    reflect: bool,
+
+    /// Checks the structure of the module before parsing it.
+    #[arg(long)]
+    validate: bool,
+
+    /// The name of the entry point to compile. Defaults to the first entry point.
+    #[arg(long)]
+    entry_point: Option<String>,
+
+    /// The stage of the entry point. Only needed if several entry points share its name.
+    #[arg(long, value_enum, requires = "entry_point")]
+    stage: Option<Stage>,
+
+    /// Overrides the value of the specialization constant with the given `SpecId`. The value may
+    /// be prefixed with its type, one of `bool:`, `i32:`, `u32:`, `i64:`, `u64:`, `f32:` or
+    /// `f64:`. Without a prefix, integers are written as is, floats as 32-bit floats, and
+    /// `true`/`false` as booleans. May be repeated.
+    #[arg(long = "spec-constant", value_name = "ID=[TYPE:]VALUE", value_parser = parse_spec_constant)]
+    spec_constants: Vec<(u32, u64)>,
+
+    #[command(flatten)]
+    common: options::Common,
+
+    #[command(flatten)]
+    glsl: options::Glsl,
+
+    #[command(flatten)]
+    hlsl: options::Hlsl,
+
+    #[command(flatten)]
+    msl: options::Msl,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum Target {
+    Glsl,
+    Hlsl,
+    Msl,
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+enum Stage {
+    Vertex,
+    TessellationControl,
+    TessellationEvaluation,
+    Geometry,
+    Fragment,
+    Compute,
+    Kernel,
+    Task,
+    Mesh,
+    RayGeneration,
+    Intersection,
+    AnyHit,
+    ClosestHit,
+    Miss,
+    Callable,
+}
+
+impl From<Stage> for spirv::ExecutionModel {
+    fn from(stage: Stage) -> Self {
+        match stage {
+            Stage::Vertex => spirv::ExecutionModel::Vertex,
+            Stage::TessellationControl => spirv::ExecutionModel::TessellationControl,
+            Stage::TessellationEvaluation => spirv::ExecutionModel::TessellationEvaluation,
+            Stage::Geometry => spirv::ExecutionModel::Geometry,
+            Stage::Fragment => spirv::ExecutionModel::Fragment,
+            Stage::Compute => spirv::ExecutionModel::GlCompute,
+            Stage::Kernel => spirv::ExecutionModel::Kernel,
+            Stage::Task => spirv::ExecutionModel::TaskNv,
+            Stage::Mesh => spirv::ExecutionModel::MeshNv,
+            Stage::RayGeneration => spirv::ExecutionModel::RayGeneration,
+            Stage::Intersection => spirv::ExecutionModel::Intersection,
+            Stage::AnyHit => spirv::ExecutionModel::AnyHit,
+            Stage::ClosestHit => spirv::ExecutionModel::ClosestHit,
+            Stage::Miss => spirv::ExecutionModel::Miss,
+            Stage::Callable => spirv::ExecutionModel::Callable,
+        }
+    }
+}
+
+fn parse_spec_constant(argument: &str) -> Result<(u32, u64), String> {
+    let (id, value) = argument
+        .split_once('=')
+        .ok_or_else(|| String::from("expected `ID=VALUE`"))?;
+    let id = id
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid specialization constant ID `{}`", id))?;
+    let value = value.trim();
+    let bits = match value.split_once(':') {
+        Some((ty, typed)) => parse_typed_value(ty, typed.trim())
+            .ok_or_else(|| format!("invalid `{}` specialization constant value `{}`", ty, typed))?,
+        None => parse_untyped_value(value)
+            .ok_or_else(|| format!("invalid specialization constant value `{}`", value))?,
+    };
+    Ok((id, bits))
+}
+
+fn parse_typed_value(ty: &str, value: &str) -> Option<u64> {
+    match ty {
+        "bool" => value.parse::<bool>().ok().map(u64::from),
+        "i32" => value
+            .parse::<i32>()
+            .ok()
+            .map(|value| u64::from(value as u32)),
+        "u32" => parse_unsigned(value).and_then(|value| u32::try_from(value).ok().map(u64::from)),
+        "i64" => value.parse::<i64>().ok().map(|value| value as u64),
+        "u64" => parse_unsigned(value),
+        "f32" => value
+            .parse::<f32>()
+            .ok()
+            .map(|value| u64::from(value.to_bits())),
+        "f64" => value.parse::<f64>().ok().map(f64::to_bits),
+        _ => None,
+    }
+}
+
+fn parse_untyped_value(value: &str) -> Option<u64> {
+    match value {
+        "true" => Some(1),
+        "false" => Some(0),
+        _ => parse_unsigned(value)
+            .or_else(|| value.parse::<i64>().ok().map(|value| value as u64))
+            .or_else(|| {
+                value
+                    .parse::<f32>()
+                    .ok()
+                    .map(|value| u64::from(value.to_bits()))
+            }),
+    }
+}
+
+fn parse_unsigned(value: &str) -> Option<u64> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// An error which stops the tool.
+enum Error {
+    Compiler(ErrorCode),
+    Usage(String),
+}
+
+impl From<ErrorCode> for Error {
+    fn from(error: ErrorCode) -> Self {
+        Error::Compiler(error)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Compiler(error) => write!(f, "{}", error),
+            Error::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(error) = run(&args) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    let module = if args.input.as_os_str() == "-" {
+        spirv::ModuleBuf::from_reader(io::stdin().lock())?
+    } else {
+        spirv::ModuleBuf::from_path(&args.input)?
+    };
+    let mut module = module.as_module();
+    if args.validate {
+        module = module.with_validation();
+    }
+
+    let output = if args.reflect {
+        let ast = Ast::<glsl::Target>::parse(&module)?;
+        let reflection = reflect::reflect(&ast)?;
+        let mut json = serde_json::to_string_pretty(&reflection)
+            .map_err(|error| Error::Usage(error.to_string()))?;
+        json.push('\n');
+        json
+    } else {
+        match args.target.expect("clap requires a target") {
+            Target::Glsl => {
+                let mut options = glsl::CompilerOptions::default();
+                args.common.apply_to_glsl(&mut options);
+                args.glsl.apply(&mut options);
+                compile::<glsl::Target>(args, &module, |ast, entry_point| {
+                    options.entry_point = entry_point;
+                    ast.set_compiler_options(&options)
+                })?
+            }
+            Target::Hlsl => {
+                let mut options = hlsl::CompilerOptions::default();
+                args.common.apply_to_hlsl(&mut options);
+                args.hlsl.apply(&mut options);
+                compile::<hlsl::Target>(args, &module, |ast, entry_point| {
+                    options.entry_point = entry_point;
+                    ast.set_compiler_options(&options)
+                })?
+            }
+            Target::Msl => {
+                let mut options = args.msl.base_options()?;
+                args.common.apply_to_msl(&mut options);
+                args.msl.apply(&mut options);
+                compile::<msl::Target>(args, &module, |ast, entry_point| {
+                    options.entry_point = entry_point;
+                    ast.set_compiler_options(&options)
+                })?
+            }
+        }
+    };
+
+    match &args.output {
+        Some(path) => std::fs::write(path, output),
+        None => io::stdout().write_all(output.as_bytes()),
+    }
+    .map_err(|error| Error::Usage(format!("failed to write output: {}", error)))
+}
+
+/// Parses and compiles `module`, letting `configure` set the options once the entry point is
+/// known.
+fn compile<TTarget>(
+    args: &Args,
+    module: &spirv::Module,
+    configure: impl FnOnce(
+        &mut Ast<TTarget>,
+        Option<(String, spirv::ExecutionModel)>,
+    ) -> Result<(), ErrorCode>,
+) -> Result<String, Error>
+where
+    TTarget: spirv::Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    let mut ast = Ast::<TTarget>::parse(module)?;
+    let entry_point = find_entry_point(args, &ast)?;
+    configure(&mut ast, entry_point)?;
+
+    let constants = ast.get_specialization_constants()?;
+    for &(constant_id, value) in &args.spec_constants {
+        let constant = constants
+            .iter()
+            .find(|constant| constant.constant_id == constant_id)
+            .ok_or_else(|| {
+                Error::Usage(format!(
+                    "the module has no specialization constant with ID {}",
+                    constant_id
+                ))
+            })?;
+        ast.set_scalar_constant(constant.id, value)?;
+    }
+
+    Ok(ast.compile()?)
+}
+
+fn find_entry_point<TTarget>(
+    args: &Args,
+    ast: &Ast<TTarget>,
+) -> Result<Option<(String, spirv::ExecutionModel)>, Error>
+where
+    TTarget: spirv::Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    let name = match &args.entry_point {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    if let Some(stage) = args.stage {
+        return Ok(Some((name.clone(), stage.into())));
+    }
+
+    let models = ast
+        .get_entry_points()?
+        .into_iter()
+        .filter(|entry_point| &entry_point.name == name)
+        .map(|entry_point| entry_point.execution_model)
+        .collect::<Vec<_>>();
+    match models.as_slice() {
+        [model] => Ok(Some((name.clone(), *model))),
+        [] => Err(Error::Usage(format!(
+            "the module has no entry point `{}`",
+            name
+        ))),
+        _ => Err(Error::Usage(format!(
+            "several entry points are named `{}`, use `--stage` to pick one",
+            name
+        ))),
+    }
+}
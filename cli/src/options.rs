@@ -0,0 +1,469 @@
+//! Flags for every field of the compiler options of each target.
+//!
+//! Every flag is optional, so that options which are not given keep their library defaults.
+
+use crate::Error;
+use clap::{Args, ValueEnum};
+use spirv_cross::{glsl, hlsl, msl};
+use std::path::PathBuf;
+
+/// Assigns each flag which was given to the option of the same name.
+macro_rules! apply {
+    ($flags:expr => $options:expr, [$($field:ident),* $(,)?]) => {
+        $(
+            if let Some(value) = $flags.$field {
+                $options.$field = value.into();
+            }
+        )*
+    };
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Options for all targets")]
+pub struct Common {
+    /// Inverts the Y coordinate of vertex positions.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub invert_y: Option<bool>,
+
+    /// Transforms clip space depth from [-1, 1] to [0, 1].
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub transform_clip_space: Option<bool>,
+
+    /// Initializes every uninitialized variable to zero.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub force_zero_initialized_variables: Option<bool>,
+}
+
+impl Common {
+    pub fn apply_to_glsl(&self, options: &mut glsl::CompilerOptions) {
+        apply!(self => options.vertex, [invert_y, transform_clip_space]);
+        apply!(self => options, [force_zero_initialized_variables]);
+    }
+
+    pub fn apply_to_hlsl(&self, options: &mut hlsl::CompilerOptions) {
+        apply!(self => options.vertex, [invert_y, transform_clip_space]);
+        apply!(self => options, [force_zero_initialized_variables]);
+    }
+
+    pub fn apply_to_msl(&self, options: &mut msl::CompilerOptions) {
+        apply!(self => options.vertex, [invert_y, transform_clip_space]);
+        apply!(self => options, [force_zero_initialized_variables]);
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "GLSL options")]
+pub struct Glsl {
+    /// The GLSL version to emit.
+    #[arg(long = "glsl-version", value_enum)]
+    pub version: Option<GlslVersion>,
+
+    /// Stores every expression in a temporary variable instead of inlining it.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub force_temporary: Option<bool>,
+
+    /// Emits GLSL for Vulkan instead of OpenGL.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub vulkan_semantics: Option<bool>,
+
+    /// Redeclares `gl_PerVertex` blocks, as needed for separate shader objects.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub separate_shader_objects: Option<bool>,
+
+    /// Flattens multidimensional arrays into one-dimensional arrays.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub flatten_multidimensional_arrays: Option<bool>,
+
+    /// Uses `GL_ARB_shading_language_420pack` for binding qualifiers before GLSL 4.20. If
+    /// disabled, binding decorations are dropped on those versions.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub enable_420_pack_extension: Option<bool>,
+
+    /// Emits push constant blocks as uniform buffers instead of plain uniforms, unless
+    /// targeting Vulkan.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub emit_push_constant_as_uniform_buffer: Option<bool>,
+
+    /// Emits uniform buffers as plain uniforms, even when the GLSL version supports them.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub emit_uniform_buffer_as_plain_uniforms: Option<bool>,
+
+    /// Emits `#line` directives for the `OpLine` instructions of the module.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub emit_line_directives: Option<bool>,
+
+    /// Deduces `readonly` and `writeonly` for storage images which have neither decoration.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub enable_storage_image_qualifier_deduction: Option<bool>,
+
+    /// Adds the base instance to `gl_InstanceIndex`, matching Vulkan for draws with a
+    /// non-zero base instance.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub support_nonzero_base_instance: Option<bool>,
+
+    /// The default precision of floats in ES fragment shaders.
+    #[arg(long, value_enum)]
+    pub default_float_precision: Option<Precision>,
+
+    /// The default precision of integers in ES fragment shaders.
+    #[arg(long, value_enum)]
+    pub default_int_precision: Option<Precision>,
+}
+
+impl Glsl {
+    pub fn apply(&self, options: &mut glsl::CompilerOptions) {
+        apply!(self => options, [
+            version,
+            force_temporary,
+            vulkan_semantics,
+            separate_shader_objects,
+            flatten_multidimensional_arrays,
+            enable_420_pack_extension,
+            emit_push_constant_as_uniform_buffer,
+            emit_uniform_buffer_as_plain_uniforms,
+            emit_line_directives,
+            enable_storage_image_qualifier_deduction,
+        ]);
+        apply!(self => options.vertex, [support_nonzero_base_instance]);
+        apply!(self => options.fragment, [default_float_precision, default_int_precision]);
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum GlslVersion {
+    #[value(name = "110")]
+    V1_10,
+    #[value(name = "120")]
+    V1_20,
+    #[value(name = "130")]
+    V1_30,
+    #[value(name = "140")]
+    V1_40,
+    #[value(name = "150")]
+    V1_50,
+    #[value(name = "330")]
+    V3_30,
+    #[value(name = "400")]
+    V4_00,
+    #[value(name = "410")]
+    V4_10,
+    #[value(name = "420")]
+    V4_20,
+    #[value(name = "430")]
+    V4_30,
+    #[value(name = "440")]
+    V4_40,
+    #[value(name = "450")]
+    V4_50,
+    #[value(name = "460")]
+    V4_60,
+    #[value(name = "100es")]
+    V1_00Es,
+    #[value(name = "300es")]
+    V3_00Es,
+    #[value(name = "310es")]
+    V3_10Es,
+    #[value(name = "320es")]
+    V3_20Es,
+}
+
+impl From<GlslVersion> for glsl::Version {
+    fn from(version: GlslVersion) -> Self {
+        match version {
+            GlslVersion::V1_10 => glsl::Version::V1_10,
+            GlslVersion::V1_20 => glsl::Version::V1_20,
+            GlslVersion::V1_30 => glsl::Version::V1_30,
+            GlslVersion::V1_40 => glsl::Version::V1_40,
+            GlslVersion::V1_50 => glsl::Version::V1_50,
+            GlslVersion::V3_30 => glsl::Version::V3_30,
+            GlslVersion::V4_00 => glsl::Version::V4_00,
+            GlslVersion::V4_10 => glsl::Version::V4_10,
+            GlslVersion::V4_20 => glsl::Version::V4_20,
+            GlslVersion::V4_30 => glsl::Version::V4_30,
+            GlslVersion::V4_40 => glsl::Version::V4_40,
+            GlslVersion::V4_50 => glsl::Version::V4_50,
+            GlslVersion::V4_60 => glsl::Version::V4_60,
+            GlslVersion::V1_00Es => glsl::Version::V1_00Es,
+            GlslVersion::V3_00Es => glsl::Version::V3_00Es,
+            GlslVersion::V3_10Es => glsl::Version::V3_10Es,
+            GlslVersion::V3_20Es => glsl::Version::V3_20Es,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Precision {
+    DontCare,
+    Low,
+    Medium,
+    High,
+}
+
+impl From<Precision> for glsl::Precision {
+    fn from(precision: Precision) -> Self {
+        match precision {
+            Precision::DontCare => glsl::Precision::DontCare,
+            Precision::Low => glsl::Precision::Low,
+            Precision::Medium => glsl::Precision::Medium,
+            Precision::High => glsl::Precision::High,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "HLSL options")]
+pub struct Hlsl {
+    /// The HLSL shader model to emit.
+    #[arg(long, value_enum)]
+    pub shader_model: Option<ShaderModel>,
+
+    /// Supports the point size built-in but ignores its value.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub point_size_compat: Option<bool>,
+
+    /// Supports the point coordinate built-in but ignores its value.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub point_coord_compat: Option<bool>,
+
+    /// Declares read-only storage buffers as UAVs instead of SRVs.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub force_storage_buffer_as_uav: Option<bool>,
+
+    /// Declares storage images which are never written as SRVs instead of UAVs.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub nonwritable_uav_texture_as_srv: Option<bool>,
+}
+
+impl Hlsl {
+    pub fn apply(&self, options: &mut hlsl::CompilerOptions) {
+        apply!(self => options, [
+            shader_model,
+            point_size_compat,
+            point_coord_compat,
+            force_storage_buffer_as_uav,
+            nonwritable_uav_texture_as_srv,
+        ]);
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum ShaderModel {
+    #[value(name = "3.0")]
+    V3_0,
+    #[value(name = "4.0")]
+    V4_0,
+    #[value(name = "4.0-level-9.0")]
+    V4_0L9_0,
+    #[value(name = "4.0-level-9.1")]
+    V4_0L9_1,
+    #[value(name = "4.0-level-9.3")]
+    V4_0L9_3,
+    #[value(name = "4.1")]
+    V4_1,
+    #[value(name = "5.0")]
+    V5_0,
+    #[value(name = "5.1")]
+    V5_1,
+    #[value(name = "6.0")]
+    V6_0,
+}
+
+impl From<ShaderModel> for hlsl::ShaderModel {
+    fn from(shader_model: ShaderModel) -> Self {
+        match shader_model {
+            ShaderModel::V3_0 => hlsl::ShaderModel::V3_0,
+            ShaderModel::V4_0 => hlsl::ShaderModel::V4_0,
+            ShaderModel::V4_0L9_0 => hlsl::ShaderModel::V4_0L9_0,
+            ShaderModel::V4_0L9_1 => hlsl::ShaderModel::V4_0L9_1,
+            ShaderModel::V4_0L9_3 => hlsl::ShaderModel::V4_0L9_3,
+            ShaderModel::V4_1 => hlsl::ShaderModel::V4_1,
+            ShaderModel::V5_0 => hlsl::ShaderModel::V5_0,
+            ShaderModel::V5_1 => hlsl::ShaderModel::V5_1,
+            ShaderModel::V6_0 => hlsl::ShaderModel::V6_0,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "MSL options")]
+pub struct Msl {
+    /// The platform to emit MSL for.
+    #[arg(long = "msl-platform", value_enum)]
+    pub platform: Option<Platform>,
+
+    /// The MSL version to emit.
+    #[arg(long = "msl-version", value_enum)]
+    pub version: Option<MslVersion>,
+
+    /// The buffer index of the texture swizzles used by `--swizzle-texture-samples`.
+    #[arg(long, value_name = "INDEX")]
+    pub swizzle_buffer_index: Option<u32>,
+
+    /// The buffer index of the indirect draw parameters of tessellation shaders.
+    #[arg(long, value_name = "INDEX")]
+    pub indirect_params_buffer_index: Option<u32>,
+
+    /// The buffer index of the outputs written by `--capture-output-to-buffer`.
+    #[arg(long, value_name = "INDEX")]
+    pub output_buffer_index: Option<u32>,
+
+    /// The buffer index of the patch outputs of tessellation control shaders.
+    #[arg(long, value_name = "INDEX")]
+    pub patch_output_buffer_index: Option<u32>,
+
+    /// The buffer index of the tessellation factors.
+    #[arg(long, value_name = "INDEX")]
+    pub tessellation_factor_buffer_index: Option<u32>,
+
+    /// The buffer index of the sizes of storage buffers with runtime-sized arrays.
+    #[arg(long, value_name = "INDEX")]
+    pub buffer_size_buffer_index: Option<u32>,
+
+    /// Emits the point size built-in when the vertex shader writes it.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub enable_point_size_builtin: Option<bool>,
+
+    /// Emits the stage outputs of vertex shaders. Disable it for vertex shaders which only
+    /// write to buffers.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub enable_rasterization: Option<bool>,
+
+    /// Writes stage outputs to the output buffer instead of returning them, as needed for
+    /// tessellation.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub capture_output_to_buffer: Option<bool>,
+
+    /// Applies the swizzle of each texture, read from the swizzle buffer, to its samples.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub swizzle_texture_samples: Option<bool>,
+
+    /// Places the origin of the tessellation domain in the lower left corner.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub tessellation_domain_origin_lower_left: Option<bool>,
+
+    /// Uses argument buffers. Requires MSL 2.0.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub enable_argument_buffers: Option<bool>,
+
+    /// Pads fragment outputs to four components so that they match any render target format.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub pad_fragment_output_components: Option<bool>,
+
+    /// Uses native arrays instead of the `spvUnsafeArray` template.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub force_native_arrays: Option<bool>,
+
+    /// Emits every resource of an argument buffer, including unused ones.
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub force_active_argument_buffer_resources: Option<bool>,
+
+    /// A JSON file of MSL compiler options, including resource binding, vertex attribute and
+    /// constant sampler overrides. Flags take precedence over the file.
+    #[arg(long, value_name = "FILE")]
+    pub msl_options: Option<PathBuf>,
+}
+
+impl Msl {
+    /// Reads the options given by `--msl-options`, or the defaults if there are none.
+    ///
+    /// The file holds `msl::CompilerOptions` as serialized by the `serde` feature of
+    /// `spirv_cross`. Fields which are omitted keep their defaults, and the override maps are
+    /// written as lists of `[location, value]` pairs:
+    ///
+    /// ```json
+    /// {
+    ///     "version": "V2_1",
+    ///     "resource_binding_overrides": [
+    ///         [
+    ///             { "stage": "Fragment", "desc_set": 0, "binding": 1 },
+    ///             { "buffer_id": 0, "texture_id": 2, "sampler_id": 2, "count": 0 }
+    ///         ]
+    ///     ],
+    ///     "const_samplers": [
+    ///         [{ "desc_set": 0, "binding": 2 }, { "min_filter": "Linear", "mag_filter": "Linear" }]
+    ///     ]
+    /// }
+    /// ```
+    pub fn base_options(&self) -> Result<msl::CompilerOptions, Error> {
+        let path = match &self.msl_options {
+            Some(path) => path,
+            None => return Ok(msl::CompilerOptions::default()),
+        };
+        let json = std::fs::read_to_string(path).map_err(|error| {
+            Error::Usage(format!("failed to read {}: {}", path.display(), error))
+        })?;
+        serde_json::from_str(&json)
+            .map_err(|error| Error::Usage(format!("invalid {}: {}", path.display(), error)))
+    }
+
+    pub fn apply(&self, options: &mut msl::CompilerOptions) {
+        apply!(self => options, [
+            platform,
+            version,
+            swizzle_buffer_index,
+            indirect_params_buffer_index,
+            output_buffer_index,
+            patch_output_buffer_index,
+            tessellation_factor_buffer_index,
+            buffer_size_buffer_index,
+            enable_point_size_builtin,
+            enable_rasterization,
+            capture_output_to_buffer,
+            swizzle_texture_samples,
+            tessellation_domain_origin_lower_left,
+            enable_argument_buffers,
+            pad_fragment_output_components,
+            force_native_arrays,
+            force_active_argument_buffer_resources,
+        ]);
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum Platform {
+    #[value(name = "ios")]
+    Ios,
+    #[value(name = "macos")]
+    MacOs,
+}
+
+impl From<Platform> for msl::Platform {
+    fn from(platform: Platform) -> Self {
+        match platform {
+            Platform::Ios => msl::Platform::iOS,
+            Platform::MacOs => msl::Platform::macOS,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum)]
+pub enum MslVersion {
+    #[value(name = "1.0")]
+    V1_0,
+    #[value(name = "1.1")]
+    V1_1,
+    #[value(name = "1.2")]
+    V1_2,
+    #[value(name = "2.0")]
+    V2_0,
+    #[value(name = "2.1")]
+    V2_1,
+    #[value(name = "2.2")]
+    V2_2,
+    #[value(name = "2.3")]
+    V2_3,
+}
+
+impl From<MslVersion> for msl::Version {
+    fn from(version: MslVersion) -> Self {
+        match version {
+            MslVersion::V1_0 => msl::Version::V1_0,
+            MslVersion::V1_1 => msl::Version::V1_1,
+            MslVersion::V1_2 => msl::Version::V1_2,
+            MslVersion::V2_0 => msl::Version::V2_0,
+            MslVersion::V2_1 => msl::Version::V2_1,
+            MslVersion::V2_2 => msl::Version::V2_2,
+            MslVersion::V2_3 => msl::Version::V2_3,
+        }
+    }
+}
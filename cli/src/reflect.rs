@@ -0,0 +1,163 @@
+//! The JSON written by `--reflect`.
+
+use serde::Serialize;
+use spirv_cross::spirv::{self, Ast, Compile, Decoration, Parse};
+use spirv_cross::ErrorCode;
+
+#[derive(Debug, Serialize)]
+pub struct Reflection {
+    entry_points: Vec<EntryPoint>,
+    resources: Resources,
+    specialization_constants: Vec<SpecializationConstant>,
+}
+
+#[derive(Debug, Serialize)]
+struct EntryPoint {
+    name: String,
+    stage: &'static str,
+    work_group_size: [u32; 3],
+}
+
+#[derive(Debug, Serialize)]
+struct Resources {
+    uniform_buffers: Vec<Resource>,
+    storage_buffers: Vec<Resource>,
+    stage_inputs: Vec<Resource>,
+    stage_outputs: Vec<Resource>,
+    subpass_inputs: Vec<Resource>,
+    storage_images: Vec<Resource>,
+    sampled_images: Vec<Resource>,
+    atomic_counters: Vec<Resource>,
+    push_constant_buffers: Vec<Resource>,
+    separate_images: Vec<Resource>,
+    separate_samplers: Vec<Resource>,
+}
+
+#[derive(Debug, Serialize)]
+struct Resource {
+    id: u32,
+    type_id: u32,
+    base_type_id: u32,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    set: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binding: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpecializationConstant {
+    id: u32,
+    constant_id: u32,
+}
+
+pub fn reflect<TTarget>(ast: &Ast<TTarget>) -> Result<Reflection, ErrorCode>
+where
+    TTarget: spirv::Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    let entry_points = ast
+        .get_entry_points()?
+        .into_iter()
+        .map(|entry_point| EntryPoint {
+            stage: stage_name(entry_point.execution_model),
+            name: entry_point.name,
+            work_group_size: [
+                entry_point.work_group_size.x,
+                entry_point.work_group_size.y,
+                entry_point.work_group_size.z,
+            ],
+        })
+        .collect();
+
+    let resources = ast.get_shader_resources()?;
+    let bound = |resources: Vec<spirv::Resource>| -> Result<Vec<Resource>, ErrorCode> {
+        resources
+            .into_iter()
+            .map(|resource| {
+                Ok(Resource {
+                    set: Some(ast.get_decoration(resource.id, Decoration::DescriptorSet)?),
+                    binding: Some(ast.get_decoration(resource.id, Decoration::Binding)?),
+                    location: None,
+                    ..Resource::from(resource)
+                })
+            })
+            .collect()
+    };
+    let located = |resources: Vec<spirv::Resource>| -> Result<Vec<Resource>, ErrorCode> {
+        resources
+            .into_iter()
+            .map(|resource| {
+                Ok(Resource {
+                    location: Some(ast.get_decoration(resource.id, Decoration::Location)?),
+                    ..Resource::from(resource)
+                })
+            })
+            .collect()
+    };
+    let unbound = |resources: Vec<spirv::Resource>| -> Vec<Resource> {
+        resources.into_iter().map(Resource::from).collect()
+    };
+
+    Ok(Reflection {
+        entry_points,
+        resources: Resources {
+            uniform_buffers: bound(resources.uniform_buffers)?,
+            storage_buffers: bound(resources.storage_buffers)?,
+            stage_inputs: located(resources.stage_inputs)?,
+            stage_outputs: located(resources.stage_outputs)?,
+            subpass_inputs: bound(resources.subpass_inputs)?,
+            storage_images: bound(resources.storage_images)?,
+            sampled_images: bound(resources.sampled_images)?,
+            atomic_counters: bound(resources.atomic_counters)?,
+            push_constant_buffers: unbound(resources.push_constant_buffers),
+            separate_images: bound(resources.separate_images)?,
+            separate_samplers: bound(resources.separate_samplers)?,
+        },
+        specialization_constants: ast
+            .get_specialization_constants()?
+            .into_iter()
+            .map(|constant| SpecializationConstant {
+                id: constant.id,
+                constant_id: constant.constant_id,
+            })
+            .collect(),
+    })
+}
+
+impl From<spirv::Resource> for Resource {
+    fn from(resource: spirv::Resource) -> Self {
+        Resource {
+            id: resource.id,
+            type_id: resource.type_id,
+            base_type_id: resource.base_type_id,
+            name: resource.name,
+            set: None,
+            binding: None,
+            location: None,
+        }
+    }
+}
+
+/// Uses the same names as `--stage`.
+fn stage_name(execution_model: spirv::ExecutionModel) -> &'static str {
+    match execution_model {
+        spirv::ExecutionModel::Vertex => "vertex",
+        spirv::ExecutionModel::TessellationControl => "tessellation-control",
+        spirv::ExecutionModel::TessellationEvaluation => "tessellation-evaluation",
+        spirv::ExecutionModel::Geometry => "geometry",
+        spirv::ExecutionModel::Fragment => "fragment",
+        spirv::ExecutionModel::GlCompute => "compute",
+        spirv::ExecutionModel::Kernel => "kernel",
+        spirv::ExecutionModel::TaskNv => "task",
+        spirv::ExecutionModel::MeshNv => "mesh",
+        spirv::ExecutionModel::RayGeneration => "ray-generation",
+        spirv::ExecutionModel::Intersection => "intersection",
+        spirv::ExecutionModel::AnyHit => "any-hit",
+        spirv::ExecutionModel::ClosestHit => "closest-hit",
+        spirv::ExecutionModel::Miss => "miss",
+        spirv::ExecutionModel::Callable => "callable",
+    }
+}
@@ -1,13 +1,11 @@
-extern crate examples;
 extern crate spirv_cross;
-use examples::words_from_bytes;
 use spirv_cross::{glsl, spirv};
 
 fn main() {
-    let module = spirv::Module::from_words(words_from_bytes(include_bytes!("../vertex.spv")));
+    let module = spirv::ModuleBuf::from_bytes(include_bytes!("../vertex.spv")).unwrap();
 
     // Parse a SPIR-V module
-    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module.as_module()).unwrap();
     let mut options = glsl::CompilerOptions::default();
     options.version = glsl::Version::V4_60;
     ast.set_compiler_options(&options).unwrap();
@@ -1,13 +1,11 @@
-extern crate examples;
 extern crate spirv_cross;
-use examples::words_from_bytes;
 use spirv_cross::{hlsl, spirv};
 
 fn main() {
-    let module = spirv::Module::from_words(words_from_bytes(include_bytes!("../vertex.spv")));
+    let module = spirv::ModuleBuf::from_bytes(include_bytes!("../vertex.spv")).unwrap();
 
     // Parse a SPIR-V module
-    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module.as_module()).unwrap();
     let mut options = hlsl::CompilerOptions::default();
     options.shader_model = hlsl::ShaderModel::V5_1;
     options.point_size_compat = false;
@@ -1,9 +0,0 @@
-#[allow(clippy::cast_ptr_alignment)]
-pub fn words_from_bytes(buf: &[u8]) -> &[u32] {
-    unsafe {
-        std::slice::from_raw_parts(
-            buf.as_ptr() as *const u32,
-            buf.len() / std::mem::size_of::<u32>(),
-        )
-    }
-}
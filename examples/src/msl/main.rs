@@ -1,13 +1,11 @@
-extern crate examples;
 extern crate spirv_cross;
-use examples::words_from_bytes;
 use spirv_cross::{msl, spirv};
 
 fn main() {
-    let module = spirv::Module::from_words(words_from_bytes(include_bytes!("../vertex.spv")));
+    let module = spirv::ModuleBuf::from_bytes(include_bytes!("../vertex.spv")).unwrap();
 
     // Parse a SPIR-V module
-    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module.as_module()).unwrap();
 
     let mut compiler_options = msl::CompilerOptions::default();
 
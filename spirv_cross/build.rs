@@ -25,6 +25,9 @@ fn main() {
         build.flag_if_supported("-std=c++14");
     }
 
+    // Without the `exceptions` feature, errors raised by SPIRV-Cross become assertions and abort
+    // the process. With it, the wrapper catches them and returns them as `ErrorCode`s.
+    #[cfg(not(feature = "exceptions"))]
     build
         .flag("-DSPIRV_CROSS_EXCEPTIONS_TO_ASSERTIONS")
         .flag("-DSPIRV_CROSS_WRAPPER_NO_EXCEPTIONS");
@@ -53,5 +56,15 @@ fn main() {
         .file("src/vendor/SPIRV-Cross/spirv_msl.cpp")
         .flag("-DSPIRV_CROSS_WRAPPER_MSL");
 
+    #[cfg(feature = "cpp")]
+    build
+        .file("src/vendor/SPIRV-Cross/spirv_cpp.cpp")
+        .flag("-DSPIRV_CROSS_WRAPPER_CPP");
+
+    #[cfg(feature = "reflect")]
+    build
+        .file("src/vendor/SPIRV-Cross/spirv_reflect.cpp")
+        .flag("-DSPIRV_CROSS_WRAPPER_REFLECT");
+
     build.compile("spirv-cross-rust-wrapper");
 }
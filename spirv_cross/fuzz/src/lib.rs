@@ -0,0 +1,200 @@
+//! Shared setup for the fuzz targets, which is also used by the regression tests in
+//! `spirv_cross/tests/fuzz_regressions.rs`.
+//!
+//! An input is a SPIR-V module in native byte order. The generator and schema words of its
+//! header, which SPIRV-Cross ignores, seed the compiler options. This lets the `.spv` files in
+//! `spirv_cross/tests/shaders` be used as seeds unchanged, while mutating those two words explores
+//! different options for the same module.
+
+use spirv_cross::{glsl, hlsl, msl, spirv};
+
+/// Parses and compiles an input to GLSL.
+pub fn glsl(data: &[u8]) {
+    let words = words(data);
+    let mut rng = Rng::new(&words);
+    let mut ast = match spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(&words)) {
+        Ok(ast) => ast,
+        Err(_) => return,
+    };
+
+    let mut options = glsl::CompilerOptions::default();
+    options.version = rng.pick(&[
+        glsl::Version::V1_10,
+        glsl::Version::V1_20,
+        glsl::Version::V1_30,
+        glsl::Version::V1_40,
+        glsl::Version::V1_50,
+        glsl::Version::V3_30,
+        glsl::Version::V4_00,
+        glsl::Version::V4_10,
+        glsl::Version::V4_20,
+        glsl::Version::V4_30,
+        glsl::Version::V4_40,
+        glsl::Version::V4_50,
+        glsl::Version::V4_60,
+        glsl::Version::V1_00Es,
+        glsl::Version::V3_00Es,
+        glsl::Version::V3_10Es,
+        glsl::Version::V3_20Es,
+    ]);
+    options.force_temporary = rng.bool();
+    options.vulkan_semantics = rng.bool();
+    options.separate_shader_objects = rng.bool();
+    options.flatten_multidimensional_arrays = rng.bool();
+    options.enable_420_pack_extension = rng.bool();
+    options.emit_push_constant_as_uniform_buffer = rng.bool();
+    options.emit_uniform_buffer_as_plain_uniforms = rng.bool();
+    options.emit_line_directives = rng.bool();
+    options.enable_storage_image_qualifier_deduction = rng.bool();
+    options.force_zero_initialized_variables = rng.bool();
+    options.vertex.invert_y = rng.bool();
+    options.vertex.transform_clip_space = rng.bool();
+    options.vertex.support_nonzero_base_instance = rng.bool();
+    let precisions = [
+        glsl::Precision::DontCare,
+        glsl::Precision::Low,
+        glsl::Precision::Medium,
+        glsl::Precision::High,
+    ];
+    options.fragment.default_float_precision = rng.pick(&precisions);
+    options.fragment.default_int_precision = rng.pick(&precisions);
+    options.entry_point = entry_point(&ast, &mut rng);
+
+    if ast.set_compiler_options(&options).is_ok() {
+        let _ = ast.compile();
+    }
+}
+
+/// Parses and compiles an input to HLSL.
+pub fn hlsl(data: &[u8]) {
+    let words = words(data);
+    let mut rng = Rng::new(&words);
+    let mut ast = match spirv::Ast::<hlsl::Target>::parse(&spirv::Module::from_words(&words)) {
+        Ok(ast) => ast,
+        Err(_) => return,
+    };
+
+    let mut options = hlsl::CompilerOptions::default();
+    options.shader_model = rng.pick(&[
+        hlsl::ShaderModel::V3_0,
+        hlsl::ShaderModel::V4_0,
+        hlsl::ShaderModel::V4_0L9_0,
+        hlsl::ShaderModel::V4_0L9_1,
+        hlsl::ShaderModel::V4_0L9_3,
+        hlsl::ShaderModel::V4_1,
+        hlsl::ShaderModel::V5_0,
+        hlsl::ShaderModel::V5_1,
+        hlsl::ShaderModel::V6_0,
+    ]);
+    options.point_size_compat = rng.bool();
+    options.point_coord_compat = rng.bool();
+    options.vertex.invert_y = rng.bool();
+    options.vertex.transform_clip_space = rng.bool();
+    options.force_storage_buffer_as_uav = rng.bool();
+    options.nonwritable_uav_texture_as_srv = rng.bool();
+    options.force_zero_initialized_variables = rng.bool();
+    options.entry_point = entry_point(&ast, &mut rng);
+
+    if ast.set_compiler_options(&options).is_ok() {
+        let _ = ast.compile();
+    }
+}
+
+/// Parses and compiles an input to MSL.
+pub fn msl(data: &[u8]) {
+    let words = words(data);
+    let mut rng = Rng::new(&words);
+    let mut ast = match spirv::Ast::<msl::Target>::parse(&spirv::Module::from_words(&words)) {
+        Ok(ast) => ast,
+        Err(_) => return,
+    };
+
+    let mut options = msl::CompilerOptions::default();
+    options.platform = rng.pick(&[msl::Platform::iOS, msl::Platform::macOS]);
+    options.version = rng.pick(&[
+        msl::Version::V1_0,
+        msl::Version::V1_1,
+        msl::Version::V1_2,
+        msl::Version::V2_0,
+        msl::Version::V2_1,
+        msl::Version::V2_2,
+        msl::Version::V2_3,
+    ]);
+    options.vertex.invert_y = rng.bool();
+    options.vertex.transform_clip_space = rng.bool();
+    options.swizzle_buffer_index = rng.below(31);
+    options.indirect_params_buffer_index = rng.below(31);
+    options.output_buffer_index = rng.below(31);
+    options.patch_output_buffer_index = rng.below(31);
+    options.tessellation_factor_buffer_index = rng.below(31);
+    options.buffer_size_buffer_index = rng.below(31);
+    options.enable_point_size_builtin = rng.bool();
+    options.enable_rasterization = rng.bool();
+    options.capture_output_to_buffer = rng.bool();
+    options.swizzle_texture_samples = rng.bool();
+    options.tessellation_domain_origin_lower_left = rng.bool();
+    options.enable_argument_buffers = rng.bool();
+    options.pad_fragment_output_components = rng.bool();
+    options.force_native_arrays = rng.bool();
+    options.force_zero_initialized_variables = rng.bool();
+    options.force_active_argument_buffer_resources = rng.bool();
+    options.entry_point = entry_point(&ast, &mut rng);
+
+    if ast.set_compiler_options(&options).is_ok() {
+        let _ = ast.compile();
+    }
+}
+
+/// Reads whole words from an input, ignoring any trailing bytes.
+fn words(data: &[u8]) -> Vec<u32> {
+    data.chunks_exact(4)
+        .map(|word| u32::from_ne_bytes([word[0], word[1], word[2], word[3]]))
+        .collect()
+}
+
+/// Picks one of the module's entry points, or none to use the default.
+fn entry_point<TTarget>(
+    ast: &spirv::Ast<TTarget>,
+    rng: &mut Rng,
+) -> Option<(String, spirv::ExecutionModel)>
+where
+    TTarget: spirv::Target,
+    spirv::Ast<TTarget>: spirv::Parse<TTarget> + spirv::Compile<TTarget>,
+{
+    let entry_points = ast.get_entry_points().ok()?;
+    let index = rng.below(entry_points.len() as u32 + 1) as usize;
+    entry_points
+        .get(index)
+        .map(|entry_point| (entry_point.name.clone(), entry_point.execution_model))
+}
+
+/// A SplitMix64 generator seeded from the module header.
+struct Rng(u64);
+
+impl Rng {
+    fn new(words: &[u32]) -> Self {
+        let generator = words.get(2).copied().unwrap_or(0) as u64;
+        let schema = words.get(4).copied().unwrap_or(0) as u64;
+        Rng(generator << 32 | schema)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: u32) -> u32 {
+        (self.next() % bound as u64) as u32
+    }
+
+    fn bool(&mut self) -> bool {
+        self.next() & 1 == 1
+    }
+
+    fn pick<T: Copy>(&mut self, values: &[T]) -> T {
+        values[self.below(values.len() as u32) as usize]
+    }
+}
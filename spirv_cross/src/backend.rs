@@ -0,0 +1,658 @@
+//! Backends selected at runtime.
+//!
+//! [`AnyAst`] wraps an [`Ast`](crate::spirv::Ast) of any enabled target, so tooling that only
+//! learns the target language at runtime can parse, reflect and compile modules without matching
+//! on the target around every call.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), spirv_cross::ErrorCode> {
+//! # #[cfg(feature = "msl")] {
+//! use spirv_cross::backend::{AnyAst, AnyCompilerOptions, TargetKind};
+//! use spirv_cross::spirv;
+//!
+//! # let words: Vec<u32> = Vec::new();
+//! let module = spirv::Module::from_words(&words);
+//! let kind: TargetKind = "msl".parse()?;
+//! let mut ast = AnyAst::parse(kind, &module)?;
+//! ast.set_compiler_options(&AnyCompilerOptions::default_for(kind))?;
+//! let resources = ast.get_shader_resources()?;
+//! let source = ast.compile()?;
+//! # }
+//! # Ok(())
+//! # }
+//! ```
+
+// Without any target enabled, every method body is an empty match.
+#![cfg_attr(
+    not(any(
+        feature = "glsl",
+        feature = "hlsl",
+        feature = "msl",
+        feature = "cpp",
+        feature = "reflect"
+    )),
+    allow(unused_imports, unused_variables, unreachable_code)
+)]
+
+#[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+use crate::cpp;
+#[cfg(feature = "glsl")]
+use crate::glsl;
+#[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+use crate::hlsl;
+#[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+use crate::msl;
+#[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+use crate::reflect;
+use crate::spirv::{
+    self, Ast, BufferRange, CompiledShader, Decoration, EntryPoint, ExecutionModel, Resource,
+    ShaderResources, SpecializationConstant, Type, WorkGroupSizeSpecializationConstants,
+};
+use crate::ErrorCode;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// A target language, as selected at runtime.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TargetKind {
+    #[cfg(feature = "glsl")]
+    Glsl,
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    Hlsl,
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    Msl,
+    #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+    Cpp,
+    #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+    Reflect,
+}
+
+impl TargetKind {
+    /// Every target enabled in this build.
+    pub const ALL: &'static [TargetKind] = &[
+        #[cfg(feature = "glsl")]
+        TargetKind::Glsl,
+        #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+        TargetKind::Hlsl,
+        #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+        TargetKind::Msl,
+        #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+        TargetKind::Cpp,
+        #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+        TargetKind::Reflect,
+    ];
+
+    /// The lowercase name of the target, as accepted by `FromStr`.
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "glsl")]
+            TargetKind::Glsl => "glsl",
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            TargetKind::Hlsl => "hlsl",
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            TargetKind::Msl => "msl",
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            TargetKind::Cpp => "cpp",
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            TargetKind::Reflect => "reflect",
+        }
+    }
+}
+
+impl fmt::Display for TargetKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TargetKind {
+    type Err = ErrorCode;
+
+    /// Parses the name of a target enabled in this build.
+    fn from_str(name: &str) -> Result<Self, ErrorCode> {
+        TargetKind::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ErrorCode::UnknownTarget(name.to_owned()))
+    }
+}
+
+/// Compiler options for any target.
+#[non_exhaustive]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnyCompilerOptions {
+    #[cfg(feature = "glsl")]
+    Glsl(glsl::CompilerOptions),
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    Hlsl(hlsl::CompilerOptions),
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    Msl(msl::CompilerOptions),
+    #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+    Cpp(cpp::CompilerOptions),
+    #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+    Reflect(reflect::CompilerOptions),
+}
+
+impl AnyCompilerOptions {
+    /// The default options of the given target.
+    pub fn default_for(kind: TargetKind) -> Self {
+        match kind {
+            #[cfg(feature = "glsl")]
+            TargetKind::Glsl => AnyCompilerOptions::Glsl(Default::default()),
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            TargetKind::Hlsl => AnyCompilerOptions::Hlsl(Default::default()),
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            TargetKind::Msl => AnyCompilerOptions::Msl(Default::default()),
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            TargetKind::Cpp => AnyCompilerOptions::Cpp(Default::default()),
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            TargetKind::Reflect => AnyCompilerOptions::Reflect(Default::default()),
+        }
+    }
+
+    /// The target these options apply to.
+    pub fn kind(&self) -> TargetKind {
+        match *self {
+            #[cfg(feature = "glsl")]
+            AnyCompilerOptions::Glsl(_) => TargetKind::Glsl,
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Hlsl(_) => TargetKind::Hlsl,
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Msl(_) => TargetKind::Msl,
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Cpp(_) => TargetKind::Cpp,
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Reflect(_) => TargetKind::Reflect,
+        }
+    }
+
+    /// The name and execution model of the entry point to use.
+    pub fn entry_point(&self) -> Option<&(String, ExecutionModel)> {
+        match *self {
+            #[cfg(feature = "glsl")]
+            AnyCompilerOptions::Glsl(ref options) => options.entry_point.as_ref(),
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Hlsl(ref options) => options.entry_point.as_ref(),
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Msl(ref options) => options.entry_point.as_ref(),
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Cpp(ref options) => options.entry_point.as_ref(),
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Reflect(ref options) => options.entry_point.as_ref(),
+        }
+    }
+
+    /// Sets the name and execution model of the entry point to use.
+    pub fn set_entry_point(&mut self, entry_point: Option<(String, ExecutionModel)>) {
+        match *self {
+            #[cfg(feature = "glsl")]
+            AnyCompilerOptions::Glsl(ref mut options) => options.entry_point = entry_point,
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Hlsl(ref mut options) => options.entry_point = entry_point,
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Msl(ref mut options) => options.entry_point = entry_point,
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Cpp(ref mut options) => options.entry_point = entry_point,
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyCompilerOptions::Reflect(ref mut options) => options.entry_point = entry_point,
+        }
+    }
+}
+
+/// An `Ast` of a target selected at runtime.
+#[non_exhaustive]
+pub enum AnyAst {
+    #[cfg(feature = "glsl")]
+    Glsl(Ast<glsl::Target>),
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    Hlsl(Ast<hlsl::Target>),
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    Msl(Ast<msl::Target>),
+    #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+    Cpp(Ast<cpp::Target>),
+    #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+    Reflect(Ast<reflect::Target>),
+}
+
+/// Calls the same method on whichever `Ast` an `AnyAst` holds.
+macro_rules! dispatch {
+    ($self:ident, ref $ast:ident => $call:expr) => {
+        match *$self {
+            #[cfg(feature = "glsl")]
+            AnyAst::Glsl(ref $ast) => $call,
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyAst::Hlsl(ref $ast) => $call,
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyAst::Msl(ref $ast) => $call,
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyAst::Cpp(ref $ast) => $call,
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyAst::Reflect(ref $ast) => $call,
+        }
+    };
+    ($self:ident, mut $ast:ident => $call:expr) => {
+        match *$self {
+            #[cfg(feature = "glsl")]
+            AnyAst::Glsl(ref mut $ast) => $call,
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyAst::Hlsl(ref mut $ast) => $call,
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyAst::Msl(ref mut $ast) => $call,
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyAst::Cpp(ref mut $ast) => $call,
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyAst::Reflect(ref mut $ast) => $call,
+        }
+    };
+}
+
+impl AnyAst {
+    /// Parses a module into an `Ast` of the given target.
+    pub fn parse(kind: TargetKind, module: &spirv::Module) -> Result<Self, ErrorCode> {
+        Ok(match kind {
+            #[cfg(feature = "glsl")]
+            TargetKind::Glsl => AnyAst::Glsl(Ast::parse(module)?),
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            TargetKind::Hlsl => AnyAst::Hlsl(Ast::parse(module)?),
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            TargetKind::Msl => AnyAst::Msl(Ast::parse(module)?),
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            TargetKind::Cpp => AnyAst::Cpp(Ast::parse(module)?),
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            TargetKind::Reflect => AnyAst::Reflect(Ast::parse(module)?),
+        })
+    }
+
+    /// Creates an `Ast` of the given target from an already parsed module.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_parsed_module(
+        kind: TargetKind,
+        module: &spirv::ParsedModule,
+    ) -> Result<Self, ErrorCode> {
+        Ok(match kind {
+            #[cfg(feature = "glsl")]
+            TargetKind::Glsl => AnyAst::Glsl(Ast::from_parsed_module(module)?),
+            #[cfg(feature = "hlsl")]
+            TargetKind::Hlsl => AnyAst::Hlsl(Ast::from_parsed_module(module)?),
+            #[cfg(feature = "msl")]
+            TargetKind::Msl => AnyAst::Msl(Ast::from_parsed_module(module)?),
+            #[cfg(feature = "cpp")]
+            TargetKind::Cpp => AnyAst::Cpp(Ast::from_parsed_module(module)?),
+            #[cfg(feature = "reflect")]
+            TargetKind::Reflect => AnyAst::Reflect(Ast::from_parsed_module(module)?),
+        })
+    }
+
+    /// The target this `Ast` compiles to.
+    pub fn kind(&self) -> TargetKind {
+        match *self {
+            #[cfg(feature = "glsl")]
+            AnyAst::Glsl(_) => TargetKind::Glsl,
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            AnyAst::Hlsl(_) => TargetKind::Hlsl,
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            AnyAst::Msl(_) => TargetKind::Msl,
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            AnyAst::Cpp(_) => TargetKind::Cpp,
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            AnyAst::Reflect(_) => TargetKind::Reflect,
+        }
+    }
+
+    /// Creates a deep copy of this `Ast`. See the `try_clone` method of each target.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn try_clone(&self) -> Result<Self, ErrorCode> {
+        Ok(match *self {
+            #[cfg(feature = "glsl")]
+            AnyAst::Glsl(ref ast) => AnyAst::Glsl(ast.try_clone()?),
+            #[cfg(feature = "hlsl")]
+            AnyAst::Hlsl(ref ast) => AnyAst::Hlsl(ast.try_clone()?),
+            #[cfg(feature = "msl")]
+            AnyAst::Msl(ref ast) => AnyAst::Msl(ast.try_clone()?),
+            #[cfg(feature = "cpp")]
+            AnyAst::Cpp(ref ast) => AnyAst::Cpp(ast.try_clone()?),
+            #[cfg(feature = "reflect")]
+            AnyAst::Reflect(ref ast) => AnyAst::Reflect(ast.try_clone()?),
+        })
+    }
+
+    /// Sets compile options. Fails with `ErrorCode::TargetMismatch` if the options are for
+    /// another target.
+    pub fn set_compiler_options(&mut self, options: &AnyCompilerOptions) -> Result<(), ErrorCode> {
+        match (&mut *self, options) {
+            #[cfg(feature = "glsl")]
+            (AnyAst::Glsl(ast), AnyCompilerOptions::Glsl(options)) => {
+                ast.set_compiler_options(options)
+            }
+            #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+            (AnyAst::Hlsl(ast), AnyCompilerOptions::Hlsl(options)) => {
+                ast.set_compiler_options(options)
+            }
+            #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+            (AnyAst::Msl(ast), AnyCompilerOptions::Msl(options)) => {
+                ast.set_compiler_options(options)
+            }
+            #[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+            (AnyAst::Cpp(ast), AnyCompilerOptions::Cpp(options)) => {
+                ast.set_compiler_options(options)
+            }
+            #[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+            (AnyAst::Reflect(ast), AnyCompilerOptions::Reflect(options)) => {
+                ast.set_compiler_options(options)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(ErrorCode::TargetMismatch {
+                expected: self.kind(),
+                found: options.kind(),
+            }),
+        }
+    }
+
+    /// Compiles the `Ast` to a `String` in its target language.
+    pub fn compile(&mut self) -> Result<String, ErrorCode> {
+        dispatch!(self, mut ast => ast.compile())
+    }
+
+    /// Compiles the `Ast` and collects the reflection data of the module.
+    pub fn compile_and_reflect(&mut self) -> Result<CompiledShader, ErrorCode> {
+        dispatch!(self, mut ast => ast.compile_and_reflect())
+    }
+
+    /// Gets a decoration.
+    pub fn get_decoration(&self, id: u32, decoration: Decoration) -> Result<u32, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_decoration(id, decoration))
+    }
+
+    /// Checks whether a decoration is set.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn has_decoration(&self, id: u32, decoration: Decoration) -> Result<bool, ErrorCode> {
+        dispatch!(self, ref ast => ast.has_decoration(id, decoration))
+    }
+
+    /// Gets every decoration set on `id`, along with its argument.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decorations(&self, id: u32) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_decorations(id))
+    }
+
+    /// Gets the argument of a `UserSemantic` decoration, see `spirv::Ast::get_decoration_string`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decoration_string(
+        &self,
+        id: u32,
+        decoration: Decoration,
+    ) -> Result<String, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_decoration_string(id, decoration))
+    }
+
+    /// Sets the argument of a `UserSemantic` decoration, see `spirv::Ast::set_decoration_string`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_decoration_string(
+        &mut self,
+        id: u32,
+        decoration: Decoration,
+        argument: &str,
+    ) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_decoration_string(id, decoration, argument))
+    }
+
+    /// Gets a name. If not defined, an empty string will be returned.
+    pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
+        dispatch!(self, mut ast => ast.get_name(id))
+    }
+
+    /// Sets a name.
+    pub fn set_name(&mut self, id: u32, name: &str) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_name(id, name))
+    }
+
+    /// Sets a member name.
+    pub fn set_member_name(&mut self, id: u32, index: u32, name: &str) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_member_name(id, index, name))
+    }
+
+    /// Unsets a decoration.
+    pub fn unset_decoration(&mut self, id: u32, decoration: Decoration) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.unset_decoration(id, decoration))
+    }
+
+    /// Sets a decoration.
+    pub fn set_decoration(
+        &mut self,
+        id: u32,
+        decoration: Decoration,
+        argument: u32,
+    ) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_decoration(id, decoration, argument))
+    }
+
+    /// Gets entry points.
+    pub fn get_entry_points(&self) -> Result<Vec<EntryPoint>, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_entry_points())
+    }
+
+    /// Gets the execution modes of an entry point.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_execution_modes(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<spirv::ExecutionModes, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_execution_modes(entry_point_name, execution_model))
+    }
+
+    /// Gets cleansed entry point names. `compile` must be called first.
+    pub fn get_cleansed_entry_point_name(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<String, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_cleansed_entry_point_name(entry_point_name, execution_model))
+    }
+
+    /// Gets active buffer ranges. Useful for push constants.
+    pub fn get_active_buffer_ranges(&self, id: u32) -> Result<Vec<BufferRange>, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_active_buffer_ranges(id))
+    }
+
+    /// Gets all specialization constants.
+    pub fn get_specialization_constants(&self) -> Result<Vec<SpecializationConstant>, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_specialization_constants())
+    }
+
+    /// Set reference of a scalar constant to a value, overriding the default.
+    ///
+    /// Can be used to override specialization constants.
+    pub fn set_scalar_constant(&mut self, id: u32, value: u64) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_scalar_constant(id, value))
+    }
+
+    /// Gets shader resources.
+    pub fn get_shader_resources(&self) -> Result<ShaderResources, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_shader_resources())
+    }
+
+    /// Gets the SPIR-V type associated with an ID.
+    pub fn get_type(&self, id: u32) -> Result<Type, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_type(id))
+    }
+
+    /// Gets the identifier for a member located at `index` within an `OpTypeStruct`.
+    pub fn get_member_name(&self, id: u32, index: u32) -> Result<String, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_member_name(id, index))
+    }
+
+    /// Gets a decoration for a member located at `index` within an `OpTypeStruct`.
+    pub fn get_member_decoration(
+        &self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<u32, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_member_decoration(id, index, decoration))
+    }
+
+    /// Sets a decoration for a member located at `index` within an `OpTypeStruct`.
+    pub fn set_member_decoration(
+        &mut self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+        argument: u32,
+    ) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.set_member_decoration(id, index, decoration, argument))
+    }
+
+    /// Unsets a decoration for a member located at `index` within an `OpTypeStruct`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn unset_member_decoration(
+        &mut self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.unset_member_decoration(id, index, decoration))
+    }
+
+    /// Gets every decoration set on the member located at `index` within an `OpTypeStruct`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_member_decorations(
+        &self,
+        id: u32,
+        index: u32,
+    ) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_member_decorations(id, index))
+    }
+
+    /// Gets the effective size of a buffer block.
+    pub fn get_declared_struct_size(&self, id: u32) -> Result<u32, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_declared_struct_size(id))
+    }
+
+    /// Gets the effective size of a buffer block struct member.
+    pub fn get_declared_struct_member_size(&self, id: u32, index: u32) -> Result<u32, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_declared_struct_member_size(id, index))
+    }
+
+    /// Gets the layout of a struct type as declared by its decorations.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_struct_layout(&self, id: u32) -> Result<spirv::StructLayout, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_struct_layout(id))
+    }
+
+    /// Compares the layout of a Rust type with the declared layout of a buffer block.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn check_block_layout<T: spirv::ShaderBlock>(
+        &self,
+        resource: &Resource,
+    ) -> Result<Vec<spirv::LayoutMismatch>, ErrorCode> {
+        dispatch!(self, ref ast => ast.check_block_layout::<T>(resource))
+    }
+
+    /// Computes the layout a struct type would have under the given rules.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn compute_struct_layout(
+        &self,
+        id: u32,
+        layout: spirv::BlockLayout,
+    ) -> Result<spirv::StructLayout, ErrorCode> {
+        dispatch!(self, ref ast => ast.compute_struct_layout(id, layout))
+    }
+
+    /// Renames an interface variable.
+    pub fn rename_interface_variable(
+        &mut self,
+        resources: &[Resource],
+        location: u32,
+        name: &str,
+    ) -> Result<(), ErrorCode> {
+        dispatch!(self, mut ast => ast.rename_interface_variable(resources, location, name))
+    }
+
+    /// Gets the active interface variables.
+    pub fn get_active_interface_variables(&mut self) -> Result<HashSet<u32>, ErrorCode> {
+        dispatch!(self, mut ast => ast.get_active_interface_variables())
+    }
+
+    /// Gets work group size specialization constants.
+    pub fn get_work_group_size_specialization_constants(
+        &self,
+    ) -> Result<WorkGroupSizeSpecializationConstants, ErrorCode> {
+        dispatch!(self, ref ast => ast.get_work_group_size_specialization_constants())
+    }
+}
+
+#[cfg(feature = "glsl")]
+impl From<Ast<glsl::Target>> for AnyAst {
+    fn from(ast: Ast<glsl::Target>) -> Self {
+        AnyAst::Glsl(ast)
+    }
+}
+
+#[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+impl From<Ast<hlsl::Target>> for AnyAst {
+    fn from(ast: Ast<hlsl::Target>) -> Self {
+        AnyAst::Hlsl(ast)
+    }
+}
+
+#[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+impl From<Ast<msl::Target>> for AnyAst {
+    fn from(ast: Ast<msl::Target>) -> Self {
+        AnyAst::Msl(ast)
+    }
+}
+
+#[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+impl From<Ast<cpp::Target>> for AnyAst {
+    fn from(ast: Ast<cpp::Target>) -> Self {
+        AnyAst::Cpp(ast)
+    }
+}
+
+#[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+impl From<Ast<reflect::Target>> for AnyAst {
+    fn from(ast: Ast<reflect::Target>) -> Self {
+        AnyAst::Reflect(ast)
+    }
+}
+
+#[cfg(feature = "glsl")]
+impl From<glsl::CompilerOptions> for AnyCompilerOptions {
+    fn from(options: glsl::CompilerOptions) -> Self {
+        AnyCompilerOptions::Glsl(options)
+    }
+}
+
+#[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+impl From<hlsl::CompilerOptions> for AnyCompilerOptions {
+    fn from(options: hlsl::CompilerOptions) -> Self {
+        AnyCompilerOptions::Hlsl(options)
+    }
+}
+
+#[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+impl From<msl::CompilerOptions> for AnyCompilerOptions {
+    fn from(options: msl::CompilerOptions) -> Self {
+        AnyCompilerOptions::Msl(options)
+    }
+}
+
+#[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+impl From<cpp::CompilerOptions> for AnyCompilerOptions {
+    fn from(options: cpp::CompilerOptions) -> Self {
+        AnyCompilerOptions::Cpp(options)
+    }
+}
+
+#[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+impl From<reflect::CompilerOptions> for AnyCompilerOptions {
+    fn from(options: reflect::CompilerOptions) -> Self {
+        AnyCompilerOptions::Reflect(options)
+    }
+}
@@ -0,0 +1,177 @@
+//! Compiles many modules in parallel.
+//!
+//! Jobs are spread over a pool of threads. Each thread parses every module it sees once per
+//! target and keeps the resulting `Ast`, so jobs which compile the same module with different
+//! options only pay for a copy of the parsed compiler instead of a new parse.
+//!
+//! Without the `exceptions` feature, SPIRV-Cross aborts the process on invalid modules, so
+//! batches of untrusted modules should enable it.
+
+use crate::spirv::{Ast, CompiledShader, Module};
+use crate::ErrorCode;
+use std::collections::hash_map::{Entry, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// The target language and its options.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Target {
+    #[cfg(feature = "glsl")]
+    Glsl(crate::glsl::CompilerOptions),
+    #[cfg(feature = "hlsl")]
+    Hlsl(crate::hlsl::CompilerOptions),
+    #[cfg(feature = "msl")]
+    Msl(crate::msl::CompilerOptions),
+}
+
+/// A module to compile and the target to compile it to.
+#[derive(Debug, Clone)]
+pub struct Job<'a> {
+    pub module: Module<'a>,
+    pub target: Target,
+}
+
+impl<'a> Job<'a> {
+    pub fn new(module: Module<'a>, target: Target) -> Self {
+        Job { module, target }
+    }
+}
+
+/// Compiles jobs on a pool of threads.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    threads: usize,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Batch::new()
+    }
+}
+
+impl Batch {
+    /// Creates a batch which uses one thread per available CPU.
+    pub fn new() -> Self {
+        Batch {
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+        }
+    }
+
+    /// Sets the number of threads. At least one thread is always used.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Compiles every job, returning the results in the same order as `jobs`.
+    ///
+    /// Jobs which share the same module words reuse the `Ast` parsed by their thread.
+    pub fn compile(&self, jobs: &[Job]) -> Vec<Result<CompiledShader, ErrorCode>> {
+        let next_job = AtomicUsize::new(0);
+        let workers = self.threads.min(jobs.len());
+
+        let finished = thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut pool = Pool::default();
+                        let mut finished = Vec::new();
+                        loop {
+                            let index = next_job.fetch_add(1, Ordering::Relaxed);
+                            match jobs.get(index) {
+                                Some(job) => finished.push((index, pool.compile(job))),
+                                None => return finished,
+                            }
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        let mut results = jobs.iter().map(|_| None).collect::<Vec<_>>();
+        for (index, result) in finished {
+            results[index] = Some(result);
+        }
+        results
+            .into_iter()
+            .map(|result| result.expect("every job is compiled once"))
+            .collect()
+    }
+}
+
+/// Compiles every job using one thread per available CPU.
+pub fn compile(jobs: &[Job]) -> Vec<Result<CompiledShader, ErrorCode>> {
+    Batch::new().compile(jobs)
+}
+
+/// Identifies the module words of a job.
+type PoolKey = (*const u32, usize);
+
+/// The parsed `Ast`s of a single thread, by target.
+#[derive(Default)]
+struct Pool {
+    #[cfg(feature = "glsl")]
+    glsl: HashMap<PoolKey, Ast<crate::glsl::Target>>,
+    #[cfg(feature = "hlsl")]
+    hlsl: HashMap<PoolKey, Ast<crate::hlsl::Target>>,
+    #[cfg(feature = "msl")]
+    msl: HashMap<PoolKey, Ast<crate::msl::Target>>,
+}
+
+impl Pool {
+    #[cfg_attr(
+        not(any(feature = "glsl", feature = "hlsl", feature = "msl")),
+        allow(unused_variables)
+    )]
+    fn compile(&mut self, job: &Job) -> Result<CompiledShader, ErrorCode> {
+        // Validation is run here rather than on parse, since a pooled `Ast` may have been
+        // parsed for a job which didn't request it.
+        job.module.validate_if_requested()?;
+        match job.target {
+            #[cfg(feature = "glsl")]
+            Target::Glsl(ref options) => {
+                let mut ast = pooled(&mut self.glsl, &job.module)?.try_clone()?;
+                ast.set_compiler_options(options)?;
+                ast.compile_and_reflect()
+            }
+            #[cfg(feature = "hlsl")]
+            Target::Hlsl(ref options) => {
+                let mut ast = pooled(&mut self.hlsl, &job.module)?.try_clone()?;
+                ast.set_compiler_options(options)?;
+                ast.compile_and_reflect()
+            }
+            #[cfg(feature = "msl")]
+            Target::Msl(ref options) => {
+                let mut ast = pooled(&mut self.msl, &job.module)?.try_clone()?;
+                ast.set_compiler_options(options)?;
+                ast.compile_and_reflect()
+            }
+        }
+    }
+}
+
+/// Gets the `Ast` parsed for the words of `module`, parsing them if there is none.
+#[cfg_attr(
+    not(any(feature = "glsl", feature = "hlsl", feature = "msl")),
+    allow(dead_code)
+)]
+fn pooled<'a, TTarget>(
+    pool: &'a mut HashMap<PoolKey, Ast<TTarget>>,
+    module: &Module,
+) -> Result<&'a Ast<TTarget>, ErrorCode>
+where
+    TTarget: crate::spirv::Target,
+    Ast<TTarget>: crate::spirv::Parse<TTarget> + crate::spirv::Compile<TTarget>,
+{
+    let words = module.words;
+    Ok(match pool.entry((words.as_ptr(), words.len())) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        // Failed parses are not pooled, so every job for the module reports the error.
+        Entry::Vacant(entry) => entry.insert(Ast::<TTarget>::parse(&Module::from_words(words))?),
+    })
+}
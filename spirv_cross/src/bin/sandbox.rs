@@ -0,0 +1,8 @@
+//! The helper process used by `spirv_cross::sandbox::Sandbox`.
+
+fn main() {
+    if let Err(error) = spirv_cross::sandbox::run_helper() {
+        eprintln!("spirv-cross-sandbox: {}", error);
+        std::process::exit(1);
+    }
+}
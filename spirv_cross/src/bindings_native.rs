@@ -2611,6 +2611,9 @@ pub mod root {
     pub type ScInternalCompilerHlsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerMsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerGlsl = ::std::os::raw::c_void;
+    pub type ScInternalCompilerReflection = ::std::os::raw::c_void;
+    pub type ScInternalCompilerCpp = ::std::os::raw::c_void;
+    pub type ScInternalParsedIr = ::std::os::raw::c_void;
     #[repr(u32)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub enum ScInternalResult {
@@ -2629,6 +2632,17 @@ pub mod root {
     }
     #[repr(C)]
     #[derive(Debug, Copy, Clone)]
+    pub struct ScExecutionModes {
+        pub modes: *mut u32,
+        pub modes_size: usize,
+        pub invocations: u32,
+        pub output_vertices: u32,
+        pub local_size_id_x: u32,
+        pub local_size_id_y: u32,
+        pub local_size_id_z: u32,
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
     pub struct ScBufferRange {
         pub index: ::std::os::raw::c_uint,
         pub offset: usize,
@@ -2754,13 +2768,35 @@ pub mod root {
         pub array_size_literal: *mut bool,
         pub array_size: usize,
         pub storage: root::spv::StorageClass,
+        pub pointer: bool,
+        pub pointer_depth: u32,
+        pub pointee_type: u32,
         pub image: root::spirv_cross::SPIRType_ImageType,
     }
+    #[repr(C)]
+    #[derive(Debug, Copy, Clone)]
+    pub struct ScArrayDimension {
+        pub size: u32,
+        pub size_literal: bool,
+        pub stride: u32,
+    }
     extern "C" {
         pub fn sc_internal_get_latest_exception_message(
             message: *mut *const ::std::os::raw::c_char,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_parsed_ir_new(
+            ir: *mut *mut root::ScInternalParsedIr,
+            words: *const u32,
+            size: usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_parsed_ir_delete(
+            ir: *mut root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_hlsl_new(
             compiler: *mut *mut root::ScInternalCompilerHlsl,
@@ -2768,6 +2804,18 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_hlsl_new_from_parsed_ir(
+            compiler: *mut *mut root::ScInternalCompilerHlsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_hlsl_clone(
+            clone: *mut *mut root::ScInternalCompilerHlsl,
+            compiler: *const root::ScInternalCompilerHlsl,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_hlsl_set_options(
             compiler: *const root::ScInternalCompilerHlsl,
@@ -2795,6 +2843,18 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_new_from_parsed_ir(
+            compiler: *mut *mut root::ScInternalCompilerMsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_msl_clone(
+            clone: *mut *mut root::ScInternalCompilerMsl,
+            compiler: *const root::ScInternalCompilerMsl,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_msl_set_options(
             compiler: *const root::ScInternalCompilerMsl,
@@ -2826,6 +2886,18 @@ pub mod root {
             size: usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_new_from_parsed_ir(
+            compiler: *mut *mut root::ScInternalCompilerGlsl,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_glsl_clone(
+            clone: *mut *mut root::ScInternalCompilerGlsl,
+            compiler: *const root::ScInternalCompilerGlsl,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_glsl_set_options(
             compiler: *const root::ScInternalCompilerGlsl,
@@ -2856,6 +2928,50 @@ pub mod root {
             id: u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_reflection_new(
+            compiler: *mut *mut root::ScInternalCompilerReflection,
+            ir: *const u32,
+            size: usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_reflection_new_from_parsed_ir(
+            compiler: *mut *mut root::ScInternalCompilerReflection,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_reflection_clone(
+            clone: *mut *mut root::ScInternalCompilerReflection,
+            compiler: *const root::ScInternalCompilerReflection,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_cpp_new(
+            compiler: *mut *mut root::ScInternalCompilerCpp,
+            ir: *const u32,
+            size: usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_cpp_new_from_parsed_ir(
+            compiler: *mut *mut root::ScInternalCompilerCpp,
+            ir: *const root::ScInternalParsedIr,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_cpp_clone(
+            clone: *mut *mut root::ScInternalCompilerCpp,
+            compiler: *const root::ScInternalCompilerCpp,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_cpp_set_interface_name(
+            compiler: *const root::ScInternalCompilerCpp,
+            name: *const ::std::os::raw::c_char,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_decoration(
             compiler: *const root::ScInternalCompilerBase,
@@ -2879,6 +2995,38 @@ pub mod root {
             decoration: root::spv::Decoration,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_has_decoration(
+            compiler: *const root::ScInternalCompilerBase,
+            result: *mut bool,
+            id: u32,
+            decoration: root::spv::Decoration,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_decoration_bitset(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            decorations: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_decoration_string(
+            compiler: *const root::ScInternalCompilerBase,
+            result: *mut *const ::std::os::raw::c_char,
+            id: u32,
+            decoration: root::spv::Decoration,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_set_decoration_string(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            decoration: root::spv::Decoration,
+            argument: *const ::std::os::raw::c_char,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_name(
             compiler: *const root::ScInternalCompilerBase,
@@ -2908,6 +3056,14 @@ pub mod root {
             size: *mut usize,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_execution_modes(
+            compiler: *const root::ScInternalCompilerBase,
+            entry_point_name: *const ::std::os::raw::c_char,
+            execution_model: root::spv::ExecutionModel,
+            execution_modes: *mut root::ScExecutionModes,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_active_buffer_ranges(
             compiler: *const root::ScInternalCompilerBase,
@@ -2978,6 +3134,23 @@ pub mod root {
             argument: u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_unset_member_decoration(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            index: u32,
+            decoration: root::spv::Decoration,
+        ) -> root::ScInternalResult;
+    }
+    extern "C" {
+        pub fn sc_internal_compiler_get_member_decoration_bitset(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            index: u32,
+            decorations: *mut *mut u32,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_get_declared_struct_size(
             compiler: *const root::ScInternalCompilerBase,
@@ -2993,6 +3166,15 @@ pub mod root {
             result: *mut u32,
         ) -> root::ScInternalResult;
     }
+    extern "C" {
+        pub fn sc_internal_compiler_get_array_layout(
+            compiler: *const root::ScInternalCompilerBase,
+            id: u32,
+            element_type: *mut u32,
+            dimensions: *mut *mut root::ScArrayDimension,
+            size: *mut usize,
+        ) -> root::ScInternalResult;
+    }
     extern "C" {
         pub fn sc_internal_compiler_rename_interface_variable(
             compiler: *const root::ScInternalCompilerBase,
@@ -2549,6 +2549,7 @@ pub mod root {
     pub type ScInternalCompilerHlsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerMsl = ::std::os::raw::c_void;
     pub type ScInternalCompilerGlsl = ::std::os::raw::c_void;
+    pub type ScInternalParsedIr = ::std::os::raw::c_void;
     #[repr(u32)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
     pub enum ScInternalResult {
@@ -2692,6 +2693,9 @@ pub mod root {
         pub array_size_literal: *mut bool,
         pub array_size: usize,
         pub storage: root::spv::StorageClass,
+        pub pointer: bool,
+        pub pointer_depth: u32,
+        pub pointee_type: u32,
         pub image: root::spirv_cross::SPIRType_ImageType,
     }
     #[repr(C)]
@@ -0,0 +1,269 @@
+//! Content-addressed caching of compiled shaders.
+//!
+//! A `Key` is a SHA-256 hash of everything which affects the output of a compilation: the module
+//! words, the target, its options (including the MSL resource, vertex attribute and sampler
+//! overrides) and the HLSL root constant layout. Compiled source and reflection data are stored under that
+//! key in memory, in a directory, or both.
+
+use crate::spirv::CompiledShader;
+use crate::ErrorCode;
+#[cfg(any(
+    feature = "glsl",
+    all(any(feature = "hlsl", feature = "msl"), not(target_arch = "wasm32"))
+))]
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Identifies the output of compiling a module with a given target and options.
+///
+/// Keys are stable between runs of a program, so they can be used to look up results stored in
+/// a directory by an earlier run. They include the version of this crate, since a different
+/// version of SPIRV-Cross may produce different output.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    /// Creates the key for compiling `module` to GLSL.
+    #[cfg(feature = "glsl")]
+    pub fn glsl(module: &crate::spirv::Module, options: &crate::glsl::CompilerOptions) -> Self {
+        Key::new(module, "glsl", options)
+    }
+
+    /// Creates the key for compiling `module` to HLSL with the given root constant layout.
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    pub fn hlsl(
+        module: &crate::spirv::Module,
+        options: &crate::hlsl::CompilerOptions,
+        root_constant_layout: &[crate::hlsl::RootConstant],
+    ) -> Self {
+        let root_constant_layout = root_constant_layout
+            .iter()
+            .map(|constant| {
+                (
+                    constant.start,
+                    constant.end,
+                    constant.binding,
+                    constant.space,
+                )
+            })
+            .collect::<Vec<_>>();
+        Key::new(module, "hlsl", &(options, root_constant_layout))
+    }
+
+    /// Creates the key for compiling `module` to MSL.
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    pub fn msl(module: &crate::spirv::Module, options: &crate::msl::CompilerOptions) -> Self {
+        Key::new(module, "msl", options)
+    }
+
+    /// Hashes the bincode encoding of everything which affects the output. Options are encoded
+    /// by their serde derives, which list every field.
+    #[cfg(any(
+        feature = "glsl",
+        all(any(feature = "hlsl", feature = "msl"), not(target_arch = "wasm32"))
+    ))]
+    fn new<T: serde::Serialize>(module: &crate::spirv::Module, target: &str, options: &T) -> Self {
+        let inputs = (
+            KEY_VERSION,
+            env!("CARGO_PKG_VERSION"),
+            target,
+            module.words,
+            options,
+        );
+        let bytes = bincode::serialize(&inputs).expect("cache key inputs can always be encoded");
+        Key(Sha256::digest(&bytes).into())
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+/// Changes whenever the inputs to a `Key`, or the order in which they are hashed, change.
+#[cfg(any(
+    feature = "glsl",
+    all(any(feature = "hlsl", feature = "msl"), not(target_arch = "wasm32"))
+))]
+const KEY_VERSION: u32 = 3;
+
+/// Stores compiled shaders by `Key`.
+///
+/// A cache may be shared between threads.
+#[derive(Debug)]
+pub struct Cache {
+    memory: Option<Mutex<HashMap<Key, CompiledShader>>>,
+    directory: Option<PathBuf>,
+}
+
+impl Cache {
+    /// Creates a cache which keeps results in memory.
+    pub fn in_memory() -> Self {
+        Cache {
+            memory: Some(Mutex::new(HashMap::new())),
+            directory: None,
+        }
+    }
+
+    /// Creates a cache which keeps results in `directory`, creating it when the first result is
+    /// stored.
+    pub fn in_directory<P: Into<PathBuf>>(directory: P) -> Self {
+        Cache {
+            memory: None,
+            directory: Some(directory.into()),
+        }
+    }
+
+    /// Also keeps results in `directory`. Results found there are kept in memory as well.
+    pub fn with_directory<P: Into<PathBuf>>(mut self, directory: P) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Gets the result stored for `key`.
+    ///
+    /// Entries in the directory which can't be read are treated as missing.
+    pub fn get(&self, key: &Key) -> Option<CompiledShader> {
+        if let Some(memory) = &self.memory {
+            if let Some(shader) = memory.lock().unwrap().get(key) {
+                return Some(shader.clone());
+            }
+        }
+
+        let shader = read_entry(&self.directory.as_ref()?.join(entry_name(key)))?;
+        if let Some(memory) = &self.memory {
+            memory.lock().unwrap().insert(*key, shader.clone());
+        }
+        Some(shader)
+    }
+
+    /// Stores the result for `key`.
+    pub fn insert(&self, key: Key, shader: CompiledShader) -> Result<(), ErrorCode> {
+        if let Some(directory) = &self.directory {
+            write_entry(directory, &key, &shader).map_err(ErrorCode::from_io)?;
+        }
+        if let Some(memory) = &self.memory {
+            memory.lock().unwrap().insert(key, shader);
+        }
+        Ok(())
+    }
+
+    /// Removes every result from memory. Results in the directory are kept.
+    pub fn clear_memory(&self) {
+        if let Some(memory) = &self.memory {
+            memory.lock().unwrap().clear();
+        }
+    }
+
+    /// Gets the result stored for `key`, or stores the result of `compile` if there is none.
+    ///
+    /// Errors returned by `compile` are not stored.
+    pub fn get_or_compile<F>(&self, key: Key, compile: F) -> Result<CompiledShader, ErrorCode>
+    where
+        F: FnOnce() -> Result<CompiledShader, ErrorCode>,
+    {
+        if let Some(shader) = self.get(&key) {
+            return Ok(shader);
+        }
+        let shader = compile()?;
+        self.insert(key, shader.clone())?;
+        Ok(shader)
+    }
+
+    /// Compiles `module` to GLSL, reusing a stored result if there is one.
+    #[cfg(feature = "glsl")]
+    pub fn compile_glsl(
+        &self,
+        module: &crate::spirv::Module,
+        options: &crate::glsl::CompilerOptions,
+    ) -> Result<CompiledShader, ErrorCode> {
+        self.get_or_compile(Key::glsl(module, options), || {
+            let mut ast = crate::spirv::Ast::<crate::glsl::Target>::parse(module)?;
+            ast.set_compiler_options(options)?;
+            ast.compile_and_reflect()
+        })
+    }
+
+    /// Compiles `module` to HLSL, reusing a stored result if there is one.
+    #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
+    pub fn compile_hlsl(
+        &self,
+        module: &crate::spirv::Module,
+        options: &crate::hlsl::CompilerOptions,
+        root_constant_layout: &[crate::hlsl::RootConstant],
+    ) -> Result<CompiledShader, ErrorCode> {
+        let key = Key::hlsl(module, options, root_constant_layout);
+        self.get_or_compile(key, || {
+            let mut ast = crate::spirv::Ast::<crate::hlsl::Target>::parse(module)?;
+            ast.set_compiler_options(options)?;
+            if !root_constant_layout.is_empty() {
+                ast.set_root_constant_layout(root_constant_layout.to_vec())?;
+            }
+            ast.compile_and_reflect()
+        })
+    }
+
+    /// Compiles `module` to MSL, reusing a stored result if there is one.
+    #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
+    pub fn compile_msl(
+        &self,
+        module: &crate::spirv::Module,
+        options: &crate::msl::CompilerOptions,
+    ) -> Result<CompiledShader, ErrorCode> {
+        self.get_or_compile(Key::msl(module, options), || {
+            let mut ast = crate::spirv::Ast::<crate::msl::Target>::parse(module)?;
+            ast.set_compiler_options(options)?;
+            ast.compile_and_reflect()
+        })
+    }
+}
+
+fn entry_name(key: &Key) -> String {
+    format!("{}.bin", key)
+}
+
+/// Identifies the build which wrote an entry. Entries are encoded with bincode from the serde
+/// derives of `CompiledShader`, so they're only read by the version of this crate which wrote them.
+const ENTRY_VERSION: &str = concat!("spirv_cross ", env!("CARGO_PKG_VERSION"));
+
+fn read_entry(path: &Path) -> Option<CompiledShader> {
+    use bincode::Options;
+
+    let bytes = fs::read(path).ok()?;
+    if bincode::deserialize::<String>(&bytes).ok()? != ENTRY_VERSION {
+        return None;
+    }
+    let (_, shader) = bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize::<(String, CompiledShader)>(&bytes)
+        .ok()?;
+    Some(shader)
+}
+
+fn write_entry(directory: &Path, key: &Key, shader: &CompiledShader) -> std::io::Result<()> {
+    let bytes = bincode::serialize(&(ENTRY_VERSION, shader))
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+
+    fs::create_dir_all(directory)?;
+    // Entries are renamed into place so that concurrent readers never see a partial entry.
+    static TEMPORARY_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let temporary = directory.join(format!(
+        "{}.{}-{}.tmp",
+        key,
+        std::process::id(),
+        TEMPORARY_COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&temporary, bytes)?;
+    let result = fs::rename(&temporary, directory.join(entry_name(key)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
@@ -0,0 +1,540 @@
+//! Generates Rust structs mirroring the declared layout of buffer blocks.
+//!
+//! Each uniform buffer, storage buffer and push constant block becomes a `#[repr(C)]` struct with
+//! explicit padding fields, followed by assertions which stop the generated code from compiling
+//! if its size or field offsets differ from the shader's. A block ending in a runtime array
+//! becomes a header struct holding the other members, together with a type for the array's
+//! elements and constants describing where they are placed.
+//!
+//! Vectors and matrices are emitted as arrays of their components, with the columns of a matrix
+//! (or its rows, when row-major) padded to the declared matrix stride. Array elements smaller
+//! than their stride are wrapped in a padded struct. `PhysicalStorageBuffer` pointers are emitted
+//! as `u64` device addresses.
+
+use crate::spirv::{Ast, Compile, Parse, Resource, StructLayout, StructMemberLayout, Target, Type};
+use crate::ErrorCode;
+use std::collections::{HashMap, HashSet};
+
+/// Generates structs for every uniform buffer, storage buffer and push constant block.
+pub fn generate<TTarget>(ast: &mut Ast<TTarget>) -> Result<String, ErrorCode>
+where
+    TTarget: Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    let resources = ast.get_shader_resources()?;
+    let blocks = resources
+        .uniform_buffers
+        .into_iter()
+        .chain(resources.storage_buffers)
+        .chain(resources.push_constant_buffers)
+        .collect::<Vec<_>>();
+    generate_blocks(ast, &blocks)
+}
+
+/// Generates structs for the given uniform buffer, storage buffer or push constant blocks.
+///
+/// Blocks sharing a type, and structs nested in several blocks, are only generated once.
+pub fn generate_blocks<TTarget>(
+    ast: &mut Ast<TTarget>,
+    resources: &[Resource],
+) -> Result<String, ErrorCode>
+where
+    TTarget: Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    let mut generator = Generator {
+        ast,
+        structs: HashMap::new(),
+        names: HashSet::new(),
+        output: String::new(),
+    };
+    for resource in resources {
+        if !generator.structs.contains_key(&resource.base_type_id) {
+            generator.block(resource)?;
+        }
+    }
+    let mut output = generator.output;
+    output.truncate(output.trim_end().len() + 1);
+    Ok(output)
+}
+
+/// A Rust type with the size and alignment it has under `#[repr(C)]`.
+#[derive(Clone)]
+struct RustType {
+    name: String,
+    size: u32,
+    align: u32,
+}
+
+impl RustType {
+    fn scalar(name: &str, size: u32) -> Self {
+        RustType {
+            name: name.to_owned(),
+            size,
+            align: size,
+        }
+    }
+
+    fn array(self, length: u32, stride: u32) -> Self {
+        RustType {
+            name: format!("[{}; {}]", self.name, length),
+            size: stride * length,
+            align: self.align,
+        }
+    }
+}
+
+struct Field {
+    name: String,
+    ty: RustType,
+    offset: u32,
+}
+
+struct Generator<'a, TTarget: Target> {
+    ast: &'a mut Ast<TTarget>,
+    /// Structs generated so far, by type ID.
+    structs: HashMap<u32, RustType>,
+    names: HashSet<String>,
+    output: String,
+}
+
+impl<'a, TTarget> Generator<'a, TTarget>
+where
+    TTarget: Target,
+    Ast<TTarget>: Parse<TTarget> + Compile<TTarget>,
+{
+    fn block(&mut self, resource: &Resource) -> Result<(), ErrorCode> {
+        let layout = self.ast.get_struct_layout(resource.base_type_id)?;
+        let name = self.struct_name(layout.type_id, &resource.name)?;
+
+        let runtime_array = layout
+            .members
+            .iter()
+            .find(|member| member.array.last() == Some(&0));
+        let runtime_array = match runtime_array {
+            Some(runtime_array) => runtime_array,
+            None => {
+                let ty = self.layout_struct(&name, &layout)?;
+                self.structs.insert(layout.type_id, ty);
+                return Ok(());
+            }
+        };
+
+        let mut fields = Vec::new();
+        for member in &layout.members {
+            if member.index != runtime_array.index {
+                fields.push(self.field(&name, member)?);
+            }
+        }
+        let header_name = self.unique_name(format!("{}Header", name));
+        let header = self.write_struct(&header_name, fields, runtime_array.offset)?;
+        self.structs.insert(layout.type_id, header);
+
+        let dimensions = runtime_array.array.len() - 1;
+        let stride = runtime_array.array_strides[dimensions];
+        let element = self.member_type(&name, runtime_array, dimensions)?;
+        let element_name = self.unique_name(format!(
+            "{}{}Element",
+            name,
+            pascal_case(&member_name(runtime_array))
+        ));
+        if element.size == stride {
+            self.output.push_str(&format!(
+                "pub type {} = {};\n\n",
+                element_name, element.name
+            ));
+        } else {
+            self.padded(&element_name, element, stride)?;
+        }
+
+        let field_name = member_name(runtime_array);
+        let constant = field_name.trim_end_matches('_').to_uppercase();
+        self.output.push_str(&format!(
+            "impl {} {{\n    \
+             /// Offset in bytes of the first element of `{}`.\n    \
+             pub const {}_OFFSET: usize = {};\n    \
+             /// Bytes between consecutive elements of `{}`.\n    \
+             pub const {}_STRIDE: usize = {};\n\
+             }}\n\n",
+            header_name, field_name, constant, runtime_array.offset, field_name, constant, stride
+        ));
+        Ok(())
+    }
+
+    fn layout_struct(&mut self, name: &str, layout: &StructLayout) -> Result<RustType, ErrorCode> {
+        let fields = layout
+            .members
+            .iter()
+            .map(|member| self.field(name, member))
+            .collect::<Result<_, _>>()?;
+        self.write_struct(name, fields, layout.size)
+    }
+
+    fn field(&mut self, parent: &str, member: &StructMemberLayout) -> Result<Field, ErrorCode> {
+        if member.array.last() == Some(&0) {
+            return Err(unrepresentable(
+                parent,
+                &member.name,
+                "runtime arrays must be the last member of a block",
+            ));
+        }
+        Ok(Field {
+            name: member_name(member),
+            ty: self.member_type(parent, member, member.array.len())?,
+            offset: member.offset,
+        })
+    }
+
+    /// Builds the type of a member with the innermost `dimensions` of its array dimensions.
+    fn member_type(
+        &mut self,
+        parent: &str,
+        member: &StructMemberLayout,
+        dimensions: usize,
+    ) -> Result<RustType, ErrorCode> {
+        let mut ty = match &member.struct_layout {
+            Some(layout) => self.nested_struct(layout)?,
+            None => self.element_type(parent, member)?,
+        };
+
+        for level in 0..dimensions {
+            if !member.array_size_literal[level] {
+                return Err(unrepresentable(
+                    parent,
+                    &member.name,
+                    "arrays sized by specialization constants have no fixed size",
+                ));
+            }
+            let stride = member.array_strides[level];
+            if stride != ty.size {
+                let suffix = if level == 0 {
+                    String::new()
+                } else {
+                    level.to_string()
+                };
+                let name = self.unique_name(format!(
+                    "{}{}Element{}",
+                    parent,
+                    pascal_case(&member_name(member)),
+                    suffix
+                ));
+                ty = self.padded(&name, ty, stride)?;
+            }
+            ty = ty.array(member.array[level], stride);
+        }
+        Ok(ty)
+    }
+
+    fn element_type(
+        &mut self,
+        parent: &str,
+        member: &StructMemberLayout,
+    ) -> Result<RustType, ErrorCode> {
+        let (scalar, vecsize, columns, pointer) = match self.ast.get_type(member.type_id)? {
+            Type::Boolean {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u32", 4), vecsize, columns, pointer),
+            Type::Int {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("i32", 4), vecsize, columns, pointer),
+            Type::UInt {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u32", 4), vecsize, columns, pointer),
+            Type::Int64 {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("i64", 8), vecsize, columns, pointer),
+            Type::UInt64 {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u64", 8), vecsize, columns, pointer),
+            Type::Half {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u16", 2), vecsize, columns, pointer),
+            Type::Float {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("f32", 4), vecsize, columns, pointer),
+            Type::Double {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("f64", 8), vecsize, columns, pointer),
+            Type::SByte {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("i8", 1), vecsize, columns, pointer),
+            Type::UByte {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u8", 1), vecsize, columns, pointer),
+            Type::Short {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("i16", 2), vecsize, columns, pointer),
+            Type::UShort {
+                vecsize,
+                columns,
+                pointer,
+                ..
+            } => (RustType::scalar("u16", 2), vecsize, columns, pointer),
+            Type::Struct {
+                pointer: Some(pointer),
+                ..
+            } => (RustType::scalar("u64", 8), 1, 1, Some(pointer)),
+            _ => {
+                return Err(unrepresentable(
+                    parent,
+                    &member.name,
+                    "its type cannot be stored in a buffer",
+                ))
+            }
+        };
+
+        if pointer.is_some() {
+            return Ok(RustType::scalar("u64", 8));
+        }
+
+        let size = scalar.size;
+        match member.matrix {
+            Some(matrix) => {
+                let (major, minor) = if matrix.row_major {
+                    (vecsize, columns)
+                } else {
+                    (columns, vecsize)
+                };
+                if matrix.stride < minor * size || matrix.stride % size != 0 {
+                    return Err(unrepresentable(
+                        parent,
+                        &member.name,
+                        "its matrix stride does not hold a whole number of components",
+                    ));
+                }
+                let vector = scalar.array(matrix.stride / size, size);
+                Ok(vector.array(major, matrix.stride))
+            }
+            None if vecsize > 1 => Ok(scalar.array(vecsize, size)),
+            None => Ok(scalar),
+        }
+    }
+
+    fn nested_struct(&mut self, layout: &StructLayout) -> Result<RustType, ErrorCode> {
+        if let Some(ty) = self.structs.get(&layout.type_id) {
+            return Ok(ty.clone());
+        }
+        let name = self.struct_name(layout.type_id, "")?;
+        let ty = self.layout_struct(&name, layout)?;
+        self.structs.insert(layout.type_id, ty.clone());
+        Ok(ty)
+    }
+
+    /// Wraps `ty` in a struct padded to `size` bytes.
+    fn padded(&mut self, name: &str, ty: RustType, size: u32) -> Result<RustType, ErrorCode> {
+        let field = Field {
+            name: "value".to_owned(),
+            ty,
+            offset: 0,
+        };
+        self.write_struct(name, vec![field], size)
+    }
+
+    fn write_struct(
+        &mut self,
+        name: &str,
+        mut fields: Vec<Field>,
+        size: u32,
+    ) -> Result<RustType, ErrorCode> {
+        fields.sort_by_key(|field| field.offset);
+        let align = fields.iter().map(|field| field.ty.align).max().unwrap_or(1);
+
+        // Different members may convert to the same field name, such as `fooBar` and `foo_bar`.
+        let mut field_names = HashSet::new();
+        for field in &mut fields {
+            field.name = unique_field_name(&mut field_names, &field.name);
+        }
+
+        let mut body = String::new();
+        let mut assertions = String::new();
+        let mut padding = 0;
+        let mut end = 0;
+        let mut pad = |body: &mut String, bytes: u32| {
+            // Skip any padding names already taken by members.
+            let name = loop {
+                let name = format!("_pad{}", padding);
+                padding += 1;
+                if !field_names.contains(&name) {
+                    break name;
+                }
+            };
+            body.push_str(&format!("    pub {}: [u8; {}],\n", name, bytes));
+        };
+
+        for field in &fields {
+            if field.offset < end {
+                return Err(unrepresentable(
+                    name,
+                    &field.name,
+                    "it overlaps the previous member",
+                ));
+            }
+            if field.offset % field.ty.align != 0 {
+                return Err(unrepresentable(
+                    name,
+                    &field.name,
+                    "its offset is not aligned to its components",
+                ));
+            }
+            if field.offset > end {
+                pad(&mut body, field.offset - end);
+            }
+            body.push_str(&format!("    pub {}: {},\n", field.name, field.ty.name));
+            assertions.push_str(&format!(
+                "const _: () = assert!(::core::mem::offset_of!({}, {}) == {});\n",
+                name, field.name, field.offset
+            ));
+            end = field.offset + field.ty.size;
+        }
+
+        if end > size {
+            return Err(ErrorCode::CompilationError(format!(
+                "cannot generate `{}`: its members extend past its size of {} bytes",
+                name, size
+            )));
+        }
+        // `#[repr(C)]` rounds the size up to the alignment, so make that padding explicit too.
+        let rust_size = size.div_ceil(align) * align;
+        if rust_size > end {
+            pad(&mut body, rust_size - end);
+        }
+
+        self.output.push_str(&format!(
+            "#[repr(C)]\n\
+             #[derive(Clone, Copy, Debug)]\n\
+             pub struct {} {{\n{}}}\n\n\
+             const _: () = assert!(::core::mem::size_of::<{}>() == {});\n{}\n",
+            name, body, name, rust_size, assertions
+        ));
+
+        Ok(RustType {
+            name: name.to_owned(),
+            size: rust_size,
+            align,
+        })
+    }
+
+    /// Names the struct for a type after its debug name, or after `fallback` if it has none.
+    fn struct_name(&mut self, type_id: u32, fallback: &str) -> Result<String, ErrorCode> {
+        let name = self.ast.get_name(type_id)?;
+        let name = pascal_case(if name.is_empty() { fallback } else { &name });
+        Ok(match name.as_str() {
+            "" => self.unique_name(format!("Struct{}", type_id)),
+            _ if self.names.contains(&name) => self.unique_name(format!("{}{}", name, type_id)),
+            _ => self.unique_name(name),
+        })
+    }
+
+    fn unique_name(&mut self, name: String) -> String {
+        let mut unique = name.clone();
+        let mut suffix = 1;
+        while !self.names.insert(unique.clone()) {
+            unique = format!("{}{}", name, suffix);
+            suffix += 1;
+        }
+        unique
+    }
+}
+
+/// Appends a number to `name` if another field of the same struct already has it.
+fn unique_field_name(names: &mut HashSet<String>, name: &str) -> String {
+    let mut unique = name.to_owned();
+    let mut suffix = 1;
+    while !names.insert(unique.clone()) {
+        unique = format!("{}_{}", name, suffix);
+        suffix += 1;
+    }
+    unique
+}
+
+fn unrepresentable(name: &str, member: &str, reason: &str) -> ErrorCode {
+    ErrorCode::CompilationError(format!(
+        "cannot generate `{}`: member `{}` is not representable because {}",
+        name, member, reason
+    ))
+}
+
+/// Converts a member name to a snake case field name.
+fn member_name(member: &StructMemberLayout) -> String {
+    let mut name = String::new();
+    let mut previous_lowercase = false;
+    for c in member.name.chars() {
+        if c.is_ascii_uppercase() && previous_lowercase {
+            name.push('_');
+        }
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        name.push(if c.is_ascii_alphanumeric() {
+            c.to_ascii_lowercase()
+        } else {
+            '_'
+        });
+    }
+    identifier(name, || format!("member{}", member.index))
+}
+
+/// Converts a type or member name to an upper camel case type name.
+fn pascal_case(name: &str) -> String {
+    let name = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect::<String>();
+    identifier(name, String::new)
+}
+
+/// Makes `name` a valid identifier which is not a keyword.
+fn identifier(name: String, fallback: impl FnOnce() -> String) -> String {
+    const KEYWORDS: &[&str] = &[
+        "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+        "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if",
+        "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+        "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+        "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    if name.is_empty() {
+        fallback()
+    } else if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
@@ -20,7 +20,18 @@ impl spirv::ExecutionModel {
             Em::ExecutionModelFragment => Ok(Fragment),
             Em::ExecutionModelGLCompute => Ok(GlCompute),
             Em::ExecutionModelKernel => Ok(Kernel),
-            _ => Err(ErrorCode::Unhandled),
+            Em::ExecutionModelTaskNV => Ok(TaskNv),
+            Em::ExecutionModelMeshNV => Ok(MeshNv),
+            Em::ExecutionModelRayGenerationKHR => Ok(RayGeneration),
+            Em::ExecutionModelIntersectionKHR => Ok(Intersection),
+            Em::ExecutionModelAnyHitKHR => Ok(AnyHit),
+            Em::ExecutionModelClosestHitKHR => Ok(ClosestHit),
+            Em::ExecutionModelMissKHR => Ok(Miss),
+            Em::ExecutionModelCallableKHR => Ok(Callable),
+            _ => Err(ErrorCode::UnsupportedEnumValue {
+                kind: "ExecutionModel",
+                raw: raw as u32,
+            }),
         }
     }
 
@@ -35,6 +46,14 @@ impl spirv::ExecutionModel {
             Fragment => Em::ExecutionModelFragment,
             GlCompute => Em::ExecutionModelGLCompute,
             Kernel => Em::ExecutionModelKernel,
+            TaskNv => Em::ExecutionModelTaskNV,
+            MeshNv => Em::ExecutionModelMeshNV,
+            RayGeneration => Em::ExecutionModelRayGenerationKHR,
+            Intersection => Em::ExecutionModelIntersectionKHR,
+            AnyHit => Em::ExecutionModelAnyHitKHR,
+            ClosestHit => Em::ExecutionModelClosestHitKHR,
+            Miss => Em::ExecutionModelMissKHR,
+            Callable => Em::ExecutionModelCallableKHR,
         }
     }
 }
@@ -69,6 +88,7 @@ impl spirv::Decoration {
             Decoration::NonWritable => D::DecorationNonWritable,
             Decoration::NonReadable => D::DecorationNonReadable,
             Decoration::Uniform => D::DecorationUniform,
+            Decoration::UniformId => D::DecorationUniformId,
             Decoration::SaturatedConversion => D::DecorationSaturatedConversion,
             Decoration::Stream => D::DecorationStream,
             Decoration::Location => D::DecorationLocation,
@@ -86,14 +106,159 @@ impl spirv::Decoration {
             Decoration::NoContraction => D::DecorationNoContraction,
             Decoration::InputAttachmentIndex => D::DecorationInputAttachmentIndex,
             Decoration::Alignment => D::DecorationAlignment,
+            Decoration::MaxByteOffset => D::DecorationMaxByteOffset,
+            Decoration::AlignmentId => D::DecorationAlignmentId,
+            Decoration::MaxByteOffsetId => D::DecorationMaxByteOffsetId,
+            Decoration::NoSignedWrap => D::DecorationNoSignedWrap,
+            Decoration::NoUnsignedWrap => D::DecorationNoUnsignedWrap,
+            Decoration::ExplicitInterpAmd => D::DecorationExplicitInterpAMD,
             Decoration::OverrideCoverageNv => D::DecorationOverrideCoverageNV,
             Decoration::PassthroughNv => D::DecorationPassthroughNV,
             Decoration::ViewportRelativeNv => D::DecorationViewportRelativeNV,
             Decoration::SecondaryViewportRelativeNv => D::DecorationSecondaryViewportRelativeNV,
+            Decoration::PerPrimitiveNv => D::DecorationPerPrimitiveNV,
+            Decoration::PerViewNv => D::DecorationPerViewNV,
+            Decoration::PerTaskNv => D::DecorationPerTaskNV,
+            Decoration::PerVertexNv => D::DecorationPerVertexNV,
+            Decoration::NonUniform => D::DecorationNonUniform,
+            Decoration::RestrictPointer => D::DecorationRestrictPointer,
+            Decoration::AliasedPointer => D::DecorationAliasedPointer,
+            Decoration::ReferencedIndirectlyIntel => D::DecorationReferencedIndirectlyINTEL,
+            Decoration::HlslCounterBuffer => D::DecorationCounterBuffer,
+            Decoration::UserSemantic => D::DecorationHlslSemanticGOOGLE,
+            Decoration::UserTypeGoogle => D::DecorationUserTypeGOOGLE,
+            Decoration::RegisterIntel => D::DecorationRegisterINTEL,
+            Decoration::MemoryIntel => D::DecorationMemoryINTEL,
+            Decoration::NumbanksIntel => D::DecorationNumbanksINTEL,
+            Decoration::BankwidthIntel => D::DecorationBankwidthINTEL,
+            Decoration::MaxPrivateCopiesIntel => D::DecorationMaxPrivateCopiesINTEL,
+            Decoration::SinglepumpIntel => D::DecorationSinglepumpINTEL,
+            Decoration::DoublepumpIntel => D::DecorationDoublepumpINTEL,
+            Decoration::MaxReplicatesIntel => D::DecorationMaxReplicatesINTEL,
+            Decoration::SimpleDualPortIntel => D::DecorationSimpleDualPortINTEL,
+            Decoration::MergeIntel => D::DecorationMergeINTEL,
+            Decoration::BankBitsIntel => D::DecorationBankBitsINTEL,
+            Decoration::ForcePow2DepthIntel => D::DecorationForcePow2DepthINTEL,
         }
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+impl spirv::ExecutionModes {
+    fn from_raw(modes: &[br::spv::ExecutionMode], raw: &br::ScExecutionModes) -> Self {
+        use crate::bindings::root::spv as S;
+        use crate::spirv::*;
+        let mut execution_modes = ExecutionModes::default();
+        for mode in modes {
+            match *mode {
+                S::ExecutionMode_ExecutionModeOriginUpperLeft => {
+                    execution_modes.origin = Some(Origin::UpperLeft)
+                }
+                S::ExecutionMode_ExecutionModeOriginLowerLeft => {
+                    execution_modes.origin = Some(Origin::LowerLeft)
+                }
+                S::ExecutionMode_ExecutionModeEarlyFragmentTests => {
+                    execution_modes.early_fragment_tests = true
+                }
+                S::ExecutionMode_ExecutionModeDepthReplacing => {
+                    execution_modes.depth_replacing = true
+                }
+                S::ExecutionMode_ExecutionModeDepthGreater => {
+                    execution_modes.depth = Some(DepthMode::Greater)
+                }
+                S::ExecutionMode_ExecutionModeDepthLess => {
+                    execution_modes.depth = Some(DepthMode::Less)
+                }
+                S::ExecutionMode_ExecutionModeDepthUnchanged => {
+                    execution_modes.depth = Some(DepthMode::Unchanged)
+                }
+                S::ExecutionMode_ExecutionModeInputPoints => {
+                    execution_modes.input_primitive = Some(InputPrimitive::Points)
+                }
+                S::ExecutionMode_ExecutionModeInputLines => {
+                    execution_modes.input_primitive = Some(InputPrimitive::Lines)
+                }
+                S::ExecutionMode_ExecutionModeInputLinesAdjacency => {
+                    execution_modes.input_primitive = Some(InputPrimitive::LinesAdjacency)
+                }
+                S::ExecutionMode_ExecutionModeTriangles => {
+                    execution_modes.input_primitive = Some(InputPrimitive::Triangles)
+                }
+                S::ExecutionMode_ExecutionModeInputTrianglesAdjacency => {
+                    execution_modes.input_primitive = Some(InputPrimitive::TrianglesAdjacency)
+                }
+                S::ExecutionMode_ExecutionModeQuads => {
+                    execution_modes.input_primitive = Some(InputPrimitive::Quads)
+                }
+                S::ExecutionMode_ExecutionModeIsolines => {
+                    execution_modes.input_primitive = Some(InputPrimitive::Isolines)
+                }
+                S::ExecutionMode_ExecutionModeOutputPoints => {
+                    execution_modes.output_primitive = Some(OutputPrimitive::Points)
+                }
+                S::ExecutionMode_ExecutionModeOutputLineStrip => {
+                    execution_modes.output_primitive = Some(OutputPrimitive::LineStrip)
+                }
+                S::ExecutionMode_ExecutionModeOutputTriangleStrip => {
+                    execution_modes.output_primitive = Some(OutputPrimitive::TriangleStrip)
+                }
+                S::ExecutionMode_ExecutionModeOutputVertices => {
+                    execution_modes.output_vertices = Some(raw.output_vertices)
+                }
+                S::ExecutionMode_ExecutionModeInvocations => {
+                    execution_modes.invocations = Some(raw.invocations)
+                }
+                S::ExecutionMode_ExecutionModeSpacingEqual => {
+                    execution_modes.spacing = Some(TessellationSpacing::Equal)
+                }
+                S::ExecutionMode_ExecutionModeSpacingFractionalEven => {
+                    execution_modes.spacing = Some(TessellationSpacing::FractionalEven)
+                }
+                S::ExecutionMode_ExecutionModeSpacingFractionalOdd => {
+                    execution_modes.spacing = Some(TessellationSpacing::FractionalOdd)
+                }
+                S::ExecutionMode_ExecutionModeVertexOrderCw => {
+                    execution_modes.winding = Some(Winding::Clockwise)
+                }
+                S::ExecutionMode_ExecutionModeVertexOrderCcw => {
+                    execution_modes.winding = Some(Winding::CounterClockwise)
+                }
+                S::ExecutionMode_ExecutionModePixelInterlockOrderedEXT => {
+                    execution_modes.interlock = Some(Interlock::PixelOrdered)
+                }
+                S::ExecutionMode_ExecutionModePixelInterlockUnorderedEXT => {
+                    execution_modes.interlock = Some(Interlock::PixelUnordered)
+                }
+                S::ExecutionMode_ExecutionModeSampleInterlockOrderedEXT => {
+                    execution_modes.interlock = Some(Interlock::SampleOrdered)
+                }
+                S::ExecutionMode_ExecutionModeSampleInterlockUnorderedEXT => {
+                    execution_modes.interlock = Some(Interlock::SampleUnordered)
+                }
+                S::ExecutionMode_ExecutionModeShadingRateInterlockOrderedEXT => {
+                    execution_modes.interlock = Some(Interlock::ShadingRateOrdered)
+                }
+                S::ExecutionMode_ExecutionModeShadingRateInterlockUnorderedEXT => {
+                    execution_modes.interlock = Some(Interlock::ShadingRateUnordered)
+                }
+                // Other modes, such as `LocalSize`, are reflected elsewhere.
+                _ => {}
+            }
+        }
+
+        // Ids are never 0, so a zeroed `LocalSizeId` means the mode isn't set.
+        if raw.local_size_id_x != 0 {
+            execution_modes.local_size_id = Some(LocalSizeId {
+                x: raw.local_size_id_x,
+                y: raw.local_size_id_y,
+                z: raw.local_size_id_z,
+            });
+        }
+
+        execution_modes
+    }
+}
+
 impl spirv::Dim {
     fn from_raw(raw: br::spv::Dim) -> Result<Self, ErrorCode> {
         use crate::bindings::root::spv::Dim as D;
@@ -106,7 +271,10 @@ impl spirv::Dim {
             D::DimRect => Ok(DimRect),
             D::DimBuffer => Ok(DimBuffer),
             D::DimSubpassData => Ok(DimSubpassData),
-            _ => Err(ErrorCode::Unhandled),
+            _ => Err(ErrorCode::UnsupportedEnumValue {
+                kind: "Dim",
+                raw: raw as u32,
+            }),
         }
     }
 }
@@ -158,7 +326,56 @@ impl spirv::ImageFormat {
             IF::ImageFormatR8ui => Ok(R8ui),
             IF::ImageFormatR64ui => Ok(R64ui),
             IF::ImageFormatR64i => Ok(R64i),
-            _ => Err(ErrorCode::Unhandled),
+            _ => Err(ErrorCode::UnsupportedEnumValue {
+                kind: "ImageFormat",
+                raw: raw as u32,
+            }),
+        }
+    }
+}
+
+impl spirv::AccessQualifier {
+    fn from_raw(raw: br::spv::AccessQualifier) -> Result<Option<Self>, ErrorCode> {
+        use crate::spirv::AccessQualifier::*;
+        match raw {
+            0 => Ok(Some(ReadOnly)),
+            1 => Ok(Some(WriteOnly)),
+            2 => Ok(Some(ReadWrite)),
+            // SPIRV-Cross uses the maximum value for images without an access qualifier.
+            0x7fff_ffff => Ok(None),
+            raw => Err(ErrorCode::UnsupportedEnumValue {
+                kind: "AccessQualifier",
+                raw,
+            }),
+        }
+    }
+}
+
+impl spirv::StorageClass {
+    fn from_raw(raw: br::spv::StorageClass) -> Self {
+        use crate::spirv::StorageClass::*;
+        match raw as u32 {
+            0 => UniformConstant,
+            1 => Input,
+            2 => Uniform,
+            3 => Output,
+            4 => Workgroup,
+            5 => CrossWorkgroup,
+            6 => Private,
+            7 => Function,
+            8 => Generic,
+            9 => PushConstant,
+            10 => AtomicCounter,
+            11 => Image,
+            12 => StorageBuffer,
+            5328 => CallableData,
+            5329 => IncomingCallableData,
+            5338 => RayPayload,
+            5339 => HitAttribute,
+            5342 => IncomingRayPayload,
+            5343 => ShaderRecordBuffer,
+            5349 => PhysicalStorageBuffer,
+            raw => Other(raw),
         }
     }
 }
@@ -173,77 +390,164 @@ impl spirv::ImageType {
             ms: ty.ms,
             sampled: ty.sampled,
             format: spirv::ImageFormat::from_raw(ty.format)?,
+            access: spirv::AccessQualifier::from_raw(ty.access)?,
         })
     }
 }
 
 impl spirv::Type {
     pub(crate) fn from_raw(
-        ty: br::spirv_cross::SPIRType_BaseType,
-        vecsize: u32,
-        columns: u32,
+        raw: &br::ScType,
         member_types: Vec<u32>,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
-        image: br::spirv_cross::SPIRType_ImageType,
     ) -> Result<Self, ErrorCode> {
         use crate::bindings::root::spirv_cross::SPIRType_BaseType as B;
         use crate::spirv::Type::*;
-        Ok(match ty {
+        let width = raw.width;
+        let vecsize = raw.vecsize;
+        let columns = raw.columns;
+        let pointer = if raw.pointer {
+            Some(spirv::PointerType {
+                storage: spirv::StorageClass::from_raw(raw.storage),
+                pointee_type_id: raw.pointee_type,
+                depth: raw.pointer_depth,
+            })
+        } else {
+            None
+        };
+        Ok(match raw.type_ {
             B::Unknown => Unknown,
             B::Void => Void,
             B::Boolean => Boolean {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
+            },
+            B::Char => Char {
+                array,
+                array_size_literal,
+                pointer,
             },
-            B::Char => Char { array, array_size_literal, },
             B::Int => Int {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
             },
             B::UInt => UInt {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
+            },
+            B::Int64 => Int64 {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::UInt64 => UInt64 {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::AtomicCounter => AtomicCounter {
+                array,
+                array_size_literal,
+                pointer,
             },
-            B::Int64 => Int64 { vecsize, array, array_size_literal, },
-            B::UInt64 => UInt64 { vecsize, array, array_size_literal, },
-            B::AtomicCounter => AtomicCounter { array, array_size_literal, },
             B::Half => Half {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
             },
             B::Float => Float {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
             },
             B::Double => Double {
+                width,
                 vecsize,
                 columns,
                 array,
                 array_size_literal,
+                pointer,
             },
             B::Struct => Struct {
                 member_types,
                 array,
                 array_size_literal,
+                pointer,
+            },
+            B::Image => Image {
+                array,
+                array_size_literal,
+                image: ImageType::from_raw(raw.image)?,
+                pointer,
+            },
+            B::SampledImage => SampledImage {
+                array,
+                array_size_literal,
+                image: ImageType::from_raw(raw.image)?,
+                pointer,
+            },
+            B::Sampler => Sampler {
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::SByte => SByte {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::UByte => UByte {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::Short => Short {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
+            },
+            B::UShort => UShort {
+                width,
+                vecsize,
+                columns,
+                array,
+                array_size_literal,
+                pointer,
             },
-            B::Image => Image { array, array_size_literal, image: ImageType::from_raw(image)? },
-            B::SampledImage => SampledImage { array, array_size_literal, image: ImageType::from_raw(image)? },
-            B::Sampler => Sampler { array, array_size_literal },
-            B::SByte => SByte { vecsize, array, array_size_literal },
-            B::UByte => UByte { vecsize, array, array_size_literal },
-            B::Short => Short { vecsize, array, array_size_literal },
-            B::UShort => UShort { vecsize, array, array_size_literal },
             B::ControlPointArray => ControlPointArray,
             B::AccelerationStructure => AccelerationStructure,
             B::RayQuery => RayQuery,
@@ -252,15 +556,34 @@ impl spirv::Type {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Compiler<TTargetData> {
     pub(crate) sc_compiler: *mut br::ScInternalCompilerBase,
+    // Only the GLSL, MSL and C++ backends keep data of their own.
+    #[cfg_attr(
+        not(any(
+            feature = "glsl",
+            all(any(feature = "msl", feature = "cpp"), not(target_arch = "wasm32"))
+        )),
+        allow(dead_code)
+    )]
     pub(crate) target_data: TTargetData,
     pub(crate) has_been_compiled: bool,
 }
 
+// SAFETY: A `Compiler` is the only owner of its SPIRV-Cross instance, and SPIRV-Cross keeps no
+// state which is shared between instances. Error messages are stored per thread by the wrapper and
+// are read back on the same thread as the failing call. Moving a compiler to another thread is
+// therefore sound. `Sync` is deliberately not implemented, since some `&self` methods mutate the
+// underlying instance.
+unsafe impl<TTargetData: Send> Send for Compiler<TTargetData> {}
+
 impl<TTargetData> Compiler<TTargetData> {
-    #[cfg(any(feature = "glsl", feature = "hlsl"))]
+    #[cfg(any(
+        feature = "glsl",
+        feature = "hlsl",
+        all(any(feature = "cpp", feature = "reflect"), not(target_arch = "wasm32"))
+    ))]
     pub fn compile(&mut self) -> Result<String, ErrorCode> {
         unsafe {
             let mut shader_ptr = ptr::null();
@@ -287,6 +610,84 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(result)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decoration_string(
+        &self,
+        id: u32,
+        decoration: spirv::Decoration,
+    ) -> Result<String, ErrorCode> {
+        unsafe {
+            let mut result_ptr = ptr::null();
+            check!(br::sc_internal_compiler_get_decoration_string(
+                self.sc_compiler,
+                &mut result_ptr,
+                id,
+                decoration.as_raw(),
+            ));
+            let result = read_string_from_ptr(result_ptr)?;
+            check!(br::sc_internal_free_pointer(result_ptr as *mut c_void));
+            Ok(result)
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_decoration_string(
+        &mut self,
+        id: u32,
+        decoration: spirv::Decoration,
+        argument: &str,
+    ) -> Result<(), ErrorCode> {
+        let argument = CString::new(argument).map_err(|_| ErrorCode::InvalidString)?;
+        unsafe {
+            check!(br::sc_internal_compiler_set_decoration_string(
+                self.sc_compiler,
+                id,
+                decoration.as_raw(),
+                argument.as_ptr(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn has_decoration(
+        &self,
+        id: u32,
+        decoration: spirv::Decoration,
+    ) -> Result<bool, ErrorCode> {
+        let mut result = false;
+        unsafe {
+            check!(br::sc_internal_compiler_has_decoration(
+                self.sc_compiler,
+                &mut result,
+                id,
+                decoration.as_raw(),
+            ));
+        }
+        Ok(result)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decorations(&self, id: u32) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        let decorations = unsafe {
+            let mut decorations = ptr::null_mut();
+            let mut size = 0;
+            check!(br::sc_internal_compiler_get_decoration_bitset(
+                self.sc_compiler,
+                id,
+                &mut decorations,
+                &mut size,
+            ));
+            read_decoration_bitset(decorations, size)?
+        };
+
+        decorations
+            .into_iter()
+            .map(|decoration| Ok((decoration, self.get_decoration(id, decoration)?)))
+            .collect()
+    }
+
     pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
         unsafe {
             let mut name_ptr = ptr::null();
@@ -312,7 +713,7 @@ impl<TTargetData> Compiler<TTargetData> {
                         name.as_ptr(),
                     ));
                 }
-                _ => return Err(ErrorCode::Unhandled),
+                _ => return Err(ErrorCode::InvalidString),
             }
         }
         Ok(())
@@ -330,7 +731,7 @@ impl<TTargetData> Compiler<TTargetData> {
                         name.as_ptr(),
                     ));
                 }
-                _ => return Err(ErrorCode::Unhandled),
+                _ => return Err(ErrorCode::InvalidString),
             }
         }
         Ok(())
@@ -447,6 +848,37 @@ impl<TTargetData> Compiler<TTargetData> {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_execution_modes(
+        &self,
+        entry_point_name: &str,
+        execution_model: spirv::ExecutionModel,
+    ) -> Result<spirv::ExecutionModes, ErrorCode> {
+        let entry_point_name =
+            CString::new(entry_point_name).map_err(|_| ErrorCode::InvalidString)?;
+        unsafe {
+            let mut execution_modes_raw = MaybeUninit::uninit();
+            check!(br::sc_internal_compiler_get_execution_modes(
+                self.sc_compiler,
+                entry_point_name.as_ptr(),
+                execution_model.as_raw(),
+                execution_modes_raw.as_mut_ptr(),
+            ));
+            let execution_modes_raw: br::ScExecutionModes = execution_modes_raw.assume_init();
+
+            let modes =
+                read_into_vec_from_ptr(execution_modes_raw.modes, execution_modes_raw.modes_size);
+            check!(br::sc_internal_free_pointer(
+                execution_modes_raw.modes as *mut c_void
+            ));
+
+            Ok(spirv::ExecutionModes::from_raw(
+                &modes,
+                &execution_modes_raw,
+            ))
+        }
+    }
+
     pub fn get_cleansed_entry_point_name(
         &self,
         entry_point_name: &str,
@@ -466,7 +898,7 @@ impl<TTargetData> Compiler<TTargetData> {
                 check!(br::sc_internal_free_pointer(cleansed_ptr as *mut c_void));
                 Ok(cleansed)
             },
-            _ => Err(ErrorCode::Unhandled),
+            _ => Err(ErrorCode::InvalidString),
         }
     }
 
@@ -533,8 +965,7 @@ impl<TTargetData> Compiler<TTargetData> {
             let member_types = read_into_vec_from_ptr(raw.member_types, raw.member_types_size);
             let array = read_into_vec_from_ptr(raw.array, raw.array_size);
             let array_size_literal = read_into_vec_from_ptr(raw.array_size_literal, raw.array_size);
-            let image = raw.image;
-            let result = Type::from_raw(raw.type_, raw.vecsize, raw.columns, member_types, array, array_size_literal, image)?;
+            let result = Type::from_raw(&raw, member_types, array, array_size_literal)?;
 
             if raw.member_types_size > 0 {
                 check!(br::sc_internal_free_pointer(
@@ -604,6 +1035,55 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(())
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn unset_member_decoration(
+        &mut self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<(), ErrorCode> {
+        unsafe {
+            check!(br::sc_internal_compiler_unset_member_decoration(
+                self.sc_compiler,
+                id,
+                index,
+                decoration.as_raw(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_member_decorations(
+        &self,
+        id: u32,
+        index: u32,
+    ) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        let decorations = unsafe {
+            let mut decorations = ptr::null_mut();
+            let mut size = 0;
+            check!(br::sc_internal_compiler_get_member_decoration_bitset(
+                self.sc_compiler,
+                id,
+                index,
+                &mut decorations,
+                &mut size,
+            ));
+            read_decoration_bitset(decorations, size)?
+        };
+
+        decorations
+            .into_iter()
+            .map(|decoration| {
+                Ok((
+                    decoration,
+                    self.get_member_decoration(id, index, decoration)?,
+                ))
+            })
+            .collect()
+    }
+
     pub fn get_declared_struct_size(&self, id: u32) -> Result<u32, ErrorCode> {
         let mut result = 0;
         unsafe {
@@ -629,6 +1109,101 @@ impl<TTargetData> Compiler<TTargetData> {
         Ok(result)
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_struct_layout(&self, id: u32) -> Result<spirv::StructLayout, ErrorCode> {
+        let size = self.get_declared_struct_size(id)?;
+        let member_types = match self.get_type(id)? {
+            Type::Struct { member_types, .. } => member_types,
+            _ => Vec::new(),
+        };
+        let members = member_types
+            .into_iter()
+            .zip(0..)
+            .map(|(type_id, index)| self.get_struct_member_layout(id, index, type_id))
+            .collect::<Result<_, _>>()?;
+
+        Ok(spirv::StructLayout {
+            type_id: id,
+            size,
+            members,
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_struct_member_layout(
+        &self,
+        id: u32,
+        index: u32,
+        type_id: u32,
+    ) -> Result<spirv::StructMemberLayout, ErrorCode> {
+        let (element_type_id, dimensions) = self.get_array_layout(type_id)?;
+
+        let (matrix, struct_layout) = match self.get_type(element_type_id)? {
+            Type::Half { columns, .. }
+            | Type::Float { columns, .. }
+            | Type::Double { columns, .. }
+                if columns > 1 =>
+            {
+                let matrix = spirv::MatrixLayout {
+                    stride: self.get_member_decoration(id, index, Decoration::MatrixStride)?,
+                    row_major: self.get_member_decoration(id, index, Decoration::RowMajor)? != 0,
+                };
+                (Some(matrix), None)
+            }
+            Type::Struct { pointer: None, .. } => {
+                (None, Some(self.get_struct_layout(element_type_id)?))
+            }
+            _ => (None, None),
+        };
+
+        Ok(spirv::StructMemberLayout {
+            index,
+            name: self.get_member_name(id, index)?,
+            type_id,
+            offset: self.get_member_decoration(id, index, Decoration::Offset)?,
+            size: self.get_declared_struct_member_size(id, index)?,
+            array: dimensions.iter().map(|dimension| dimension.size).collect(),
+            array_size_literal: dimensions
+                .iter()
+                .map(|dimension| dimension.size_literal)
+                .collect(),
+            array_strides: dimensions
+                .iter()
+                .map(|dimension| dimension.stride)
+                .collect(),
+            matrix,
+            struct_layout,
+        })
+    }
+
+    /// Gets the element type of an array type along with its dimensions, innermost first.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_array_layout(
+        &self,
+        type_id: u32,
+    ) -> Result<(u32, Vec<br::ScArrayDimension>), ErrorCode> {
+        unsafe {
+            let mut element_type_id = 0;
+            let mut dimensions = ptr::null_mut();
+            let mut size = 0;
+            check!(br::sc_internal_compiler_get_array_layout(
+                self.sc_compiler,
+                type_id,
+                &mut element_type_id,
+                &mut dimensions,
+                &mut size,
+            ));
+            let result = read_into_vec_from_ptr(dimensions, size);
+            check!(br::sc_internal_free_pointer(dimensions as *mut c_void));
+            Ok((element_type_id, result))
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_array_element_type(&self, type_id: u32) -> Result<u32, ErrorCode> {
+        Ok(self.get_array_layout(type_id)?.0)
+    }
+
     pub fn get_shader_resources(&self) -> Result<spirv::ShaderResources, ErrorCode> {
         unsafe {
             let mut shader_resources_raw = MaybeUninit::uninit();
@@ -719,11 +1294,11 @@ impl<TTargetData> Compiler<TTargetData> {
             for resource in resources.iter() {
                 match CString::new(&*resource.name) {
                     Ok(rn) => resources_names.push(rn),
-                    Err(_) => return Err(ErrorCode::Unhandled),
+                    Err(_) => return Err(ErrorCode::InvalidString),
                 }
             }
 
-            let new_name = CString::new(new_name).map_err(|_| ErrorCode::Unhandled)?;
+            let new_name = CString::new(new_name).map_err(|_| ErrorCode::InvalidString)?;
             let new_name_ptr = new_name.as_ptr();
             let resources = resources
                 .iter()
@@ -788,6 +1363,20 @@ impl<TTargetData> Compiler<TTargetData> {
     }
 }
 
+/// Reads the decorations listed by the wrapper, skipping any which `Decoration` doesn't know of.
+#[cfg(not(target_arch = "wasm32"))]
+unsafe fn read_decoration_bitset(
+    decorations: *mut u32,
+    size: usize,
+) -> Result<Vec<Decoration>, ErrorCode> {
+    let raw = read_into_vec_from_ptr(decorations, size);
+    check!(br::sc_internal_free_pointer(decorations as *mut c_void));
+    Ok(raw
+        .into_iter()
+        .filter_map(|decoration| Decoration::from_raw(decoration).ok())
+        .collect())
+}
+
 impl<TTargetData> Drop for Compiler<TTargetData> {
     fn drop(&mut self) {
         unsafe {
@@ -0,0 +1,148 @@
+use crate::bindings as br;
+use crate::{compiler, spirv, ErrorCode};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::ptr;
+
+/// A C++ target.
+///
+/// Compiling to this target produces C++ source which runs the shader on the CPU through the
+/// `spirv_cross/internal_interface.hpp` header shipped with SPIRV-Cross.
+#[derive(Debug, Clone)]
+pub enum Target {}
+
+#[derive(Clone)]
+pub struct TargetData {
+    interface_name: Option<CString>,
+}
+
+impl spirv::Target for Target {
+    type Data = TargetData;
+}
+
+/// C++ compiler options.
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CompilerOptions {
+    /// The name of the generated shader interface. If no name is specified, then SPIRV-Cross'
+    /// default is used.
+    pub interface_name: Option<String>,
+    /// The name and execution model of the entry point to use. If no entry
+    /// point is specified, then the first entry point found will be used.
+    pub entry_point: Option<(String, spirv::ExecutionModel)>,
+}
+
+impl spirv::Parse<Target> for spirv::Ast<Target> {
+    fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
+        module.validate_if_requested()?;
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_cpp_new(
+                &mut compiler,
+                module.words.as_ptr(),
+                module.words.len(),
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+
+        Ok(new_ast(compiler))
+    }
+}
+
+impl spirv::FromParsedModule<Target> for spirv::Ast<Target> {
+    fn from_parsed_module(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_cpp_new_from_parsed_ir(
+                &mut compiler,
+                module.ir,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+
+        Ok(new_ast(compiler))
+    }
+}
+
+fn new_ast(sc_compiler: *mut br::ScInternalCompilerCpp) -> spirv::Ast<Target> {
+    spirv::Ast {
+        compiler: compiler::Compiler {
+            sc_compiler,
+            target_data: TargetData {
+                interface_name: None,
+            },
+            has_been_compiled: false,
+        },
+        target_type: PhantomData,
+    }
+}
+
+impl spirv::Compile<Target> for spirv::Ast<Target> {
+    type CompilerOptions = CompilerOptions;
+
+    /// Set C++ compiler specific compilation settings.
+    fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
+        if let Some((name, model)) = &options.entry_point {
+            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::InvalidString)?;
+            let model = model.as_raw();
+            unsafe {
+                check!(br::sc_internal_compiler_set_entry_point(
+                    self.compiler.sc_compiler,
+                    name_raw.as_ptr(),
+                    model,
+                ));
+            }
+        };
+
+        if let Some(name) = &options.interface_name {
+            let name = CString::new(name.as_str()).map_err(|_| ErrorCode::InvalidString)?;
+            self.compiler.target_data.interface_name = Some(name);
+        }
+        self.apply_interface_name()
+    }
+
+    /// Generate C++ source from the AST.
+    fn compile(&mut self) -> Result<String, ErrorCode> {
+        self.compiler.compile()
+    }
+}
+
+impl spirv::Ast<Target> {
+    /// Creates a deep copy of this `Ast`, including its renames, decorations, specialization
+    /// constants and options, which can then be configured and compiled independently.
+    pub fn try_clone(&self) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_cpp_clone(
+                &mut sc_compiler,
+                self.compiler.sc_compiler,
+            ));
+        }
+
+        let clone = spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: self.compiler.target_data.clone(),
+                has_been_compiled: self.compiler.has_been_compiled,
+            },
+            target_type: PhantomData,
+        };
+        clone.apply_interface_name()?;
+        Ok(clone)
+    }
+
+    fn apply_interface_name(&self) -> Result<(), ErrorCode> {
+        if let Some(name) = &self.compiler.target_data.interface_name {
+            unsafe {
+                check!(br::sc_internal_compiler_cpp_set_interface_name(
+                    self.compiler.sc_compiler,
+                    name.as_ptr(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
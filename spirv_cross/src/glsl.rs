@@ -9,6 +9,7 @@ use std::ptr;
 #[derive(Debug, Clone)]
 pub enum Target {}
 
+#[derive(Clone)]
 pub struct TargetData {
     combined_image_samplers_built: bool,
 }
@@ -19,6 +20,7 @@ impl spirv::Target for Target {
 
 #[allow(non_snake_case, non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Version {
     V1_10,
@@ -40,7 +42,9 @@ pub enum Version {
     V3_20Es,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerVertexOptions {
     pub invert_y: bool,
     pub transform_clip_space: bool,
@@ -59,6 +63,7 @@ impl Default for CompilerVertexOptions {
 
 // Note: These values should match with `CompilerGLSL::Options::Precision`.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum Precision {
     DontCare = 0,
@@ -67,7 +72,9 @@ pub enum Precision {
     High = 3,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerFragmentOptions {
     pub default_float_precision: Precision,
     pub default_int_precision: Precision,
@@ -84,7 +91,9 @@ impl Default for CompilerFragmentOptions {
 
 /// GLSL compiler options.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerOptions {
     pub version: Version,
     pub force_temporary: bool,
@@ -128,29 +137,47 @@ impl Default for CompilerOptions {
 
 impl spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
-        let compiler = {
-            let mut compiler = ptr::null_mut();
-            unsafe {
-                check!(br::sc_internal_compiler_glsl_new(
-                    &mut compiler,
-                    module.words.as_ptr() as *const u32,
-                    module.words.len() as usize,
-                ));
-            }
+        module.validate_if_requested()?;
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_glsl_new(
+                &mut compiler,
+                module.words.as_ptr() as *const u32,
+                module.words.len() as usize,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
 
-            compiler::Compiler {
-                sc_compiler: compiler,
-                target_data: TargetData {
-                    combined_image_samplers_built: false,
-                },
-                has_been_compiled: false,
-            }
-        };
+        Ok(new_ast(compiler))
+    }
+}
 
-        Ok(spirv::Ast {
-            compiler,
-            target_type: PhantomData,
-        })
+#[cfg(not(target_arch = "wasm32"))]
+impl spirv::FromParsedModule<Target> for spirv::Ast<Target> {
+    fn from_parsed_module(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_glsl_new_from_parsed_ir(
+                &mut compiler,
+                module.ir,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+
+        Ok(new_ast(compiler))
+    }
+}
+
+fn new_ast(sc_compiler: *mut br::ScInternalCompilerGlsl) -> spirv::Ast<Target> {
+    spirv::Ast {
+        compiler: compiler::Compiler {
+            sc_compiler,
+            target_data: TargetData {
+                combined_image_samplers_built: false,
+            },
+            has_been_compiled: false,
+        },
+        target_type: PhantomData,
     }
 }
 
@@ -160,7 +187,7 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
     /// Set GLSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
         if let Some((name, model)) = &options.entry_point {
-            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
+            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::InvalidString)?;
             let model = model.as_raw();
             unsafe {
                 check!(br::sc_internal_compiler_set_entry_point(
@@ -231,6 +258,28 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 }
 
 impl spirv::Ast<Target> {
+    /// Creates a deep copy of this `Ast`, including its renames, decorations, specialization
+    /// constants and options, which can then be configured and compiled independently.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn try_clone(&self) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_glsl_clone(
+                &mut sc_compiler,
+                self.compiler.sc_compiler,
+            ));
+        }
+
+        Ok(spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: self.compiler.target_data.clone(),
+                has_been_compiled: self.compiler.has_been_compiled,
+            },
+            target_type: PhantomData,
+        })
+    }
+
     pub fn build_combined_image_samplers(&mut self) -> Result<(), ErrorCode> {
         unsafe {
             if !self.compiler.target_data.combined_image_samplers_built {
@@ -281,7 +330,7 @@ impl spirv::Ast<Target> {
                         line.as_ptr(),
                     ));
                 }
-                _ => return Err(ErrorCode::Unhandled),
+                _ => return Err(ErrorCode::InvalidString),
             }
 
             Ok(())
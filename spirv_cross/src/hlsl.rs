@@ -17,6 +17,7 @@ impl spirv::Target for Target {
 /// A HLSL shader model version.
 #[allow(non_snake_case, non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum ShaderModel {
     V3_0,
@@ -48,7 +49,9 @@ impl ShaderModel {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerVertexOptions {
     pub invert_y: bool,
     pub transform_clip_space: bool,
@@ -65,7 +68,9 @@ impl Default for CompilerVertexOptions {
 
 /// HLSL compiler options.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerOptions {
     pub shader_model: ShaderModel,
     /// Support point size builtin but ignore the value.
@@ -99,27 +104,44 @@ impl Default for CompilerOptions {
 
 impl spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
-        let compiler = {
-            let mut compiler = ptr::null_mut();
-            unsafe {
-                check!(br::sc_internal_compiler_hlsl_new(
-                    &mut compiler,
-                    module.words.as_ptr() as *const u32,
-                    module.words.len() as usize,
-                ));
-            }
+        module.validate_if_requested()?;
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_hlsl_new(
+                &mut compiler,
+                module.words.as_ptr() as *const u32,
+                module.words.len() as usize,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
 
-            compiler::Compiler {
-                sc_compiler: compiler,
-                target_data: (),
-                has_been_compiled: false,
-            }
-        };
+        Ok(new_ast(compiler))
+    }
+}
 
-        Ok(spirv::Ast {
-            compiler,
-            target_type: PhantomData,
-        })
+impl spirv::FromParsedModule<Target> for spirv::Ast<Target> {
+    fn from_parsed_module(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_hlsl_new_from_parsed_ir(
+                &mut compiler,
+                module.ir,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+
+        Ok(new_ast(compiler))
+    }
+}
+
+fn new_ast(sc_compiler: *mut br::ScInternalCompilerHlsl) -> spirv::Ast<Target> {
+    spirv::Ast {
+        compiler: compiler::Compiler {
+            sc_compiler,
+            target_data: (),
+            has_been_compiled: false,
+        },
+        target_type: PhantomData,
     }
 }
 
@@ -129,7 +151,7 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
     /// Set HLSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
         if let Some((name, model)) = &options.entry_point {
-            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
+            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::InvalidString)?;
             let model = model.as_raw();
             unsafe {
                 check!(br::sc_internal_compiler_set_entry_point(
@@ -166,6 +188,27 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 }
 
 impl spirv::Ast<Target> {
+    /// Creates a deep copy of this `Ast`, including its renames, decorations, specialization
+    /// constants and options, which can then be configured and compiled independently.
+    pub fn try_clone(&self) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_hlsl_clone(
+                &mut sc_compiler,
+                self.compiler.sc_compiler,
+            ));
+        }
+
+        Ok(spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: (),
+                has_been_compiled: self.compiler.has_been_compiled,
+            },
+            target_type: PhantomData,
+        })
+    }
+
     ///
     pub fn set_root_constant_layout(&mut self, layout: Vec<RootConstant>) -> Result<(), ErrorCode> {
         unsafe {
@@ -8,49 +8,35 @@ macro_rules! check {
 #[cfg(not(target_arch = "wasm32"))]
 macro_rules! check {
     ($check:expr) => {{
-        let result = $check;
-        if br::ScInternalResult::Success != result {
-            if br::ScInternalResult::CompilationError == result {
-                let mut message_ptr = ptr::null();
-
-                if br::ScInternalResult::Success
-                    != br::sc_internal_get_latest_exception_message(&mut message_ptr)
-                {
-                    return Err(ErrorCode::Unhandled);
-                }
-
-                let message = match std::ffi::CStr::from_ptr(message_ptr)
-                    .to_owned()
-                    .into_string()
-                {
-                    Err(_) => return Err(ErrorCode::Unhandled),
-                    Ok(v) => v,
-                };
-
-                if br::ScInternalResult::Success
-                    != br::sc_internal_free_pointer(message_ptr as *mut std::os::raw::c_void)
-                {
-                    return Err(ErrorCode::Unhandled);
-                }
-
-                return Err(ErrorCode::CompilationError(message));
-            }
-
-            return Err(ErrorCode::Unhandled);
-        }
+        crate::check_result($check)?
     }};
 }
 
+pub mod backend;
 mod compiler;
 
+#[cfg(all(feature = "cpp", not(target_arch = "wasm32")))]
+pub mod cpp;
 #[cfg(feature = "glsl")]
 pub mod glsl;
 #[cfg(all(feature = "hlsl", not(target_arch = "wasm32")))]
 pub mod hlsl;
 #[cfg(all(feature = "msl", not(target_arch = "wasm32")))]
 pub mod msl;
+#[cfg(all(feature = "reflect", not(target_arch = "wasm32")))]
+pub mod reflect;
 
 pub mod spirv;
+mod validation;
+
+#[cfg(all(feature = "batch", not(target_arch = "wasm32")))]
+pub mod batch;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(all(feature = "codegen", not(target_arch = "wasm32")))]
+pub mod codegen;
+#[cfg(all(feature = "sandbox", not(target_arch = "wasm32")))]
+pub mod sandbox;
 
 #[cfg(target_arch = "wasm32")]
 pub(crate) mod emscripten;
@@ -80,16 +66,228 @@ mod bindings {
     pub use root::*;
 }
 
+/// An error raised by the wrapper or by SPIRV-Cross.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum ErrorCode {
+    /// An unexpected failure for which no further information is available.
     Unhandled,
+    /// SPIRV-Cross failed to compile or reflect the module.
     CompilationError(String),
+    /// SPIRV-Cross failed to parse the module.
+    InvalidModule(String),
+    /// SPIRV-Cross reported an enum value which has no equivalent in this crate.
+    UnsupportedEnumValue {
+        // serde borrows fields written as `&str` from the input, which would limit deserializing
+        // errors to `'static` input. The full path opts out of that.
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "enum_kind::deserialize"))]
+        kind: &'static std::primitive::str,
+        raw: u32,
+    },
+    /// A string contained an interior NUL byte or was not valid UTF-8.
+    InvalidString,
+    /// The operation requires `compile` to be called first.
+    NotCompiled,
+    /// The backend failed with an exception other than a SPIRV-Cross compiler error.
+    Backend(String),
+    /// The module header was rejected before the module reached SPIRV-Cross.
+    InvalidHeader(spirv::HeaderError),
+    /// The module failed validation at the given word offset.
+    Validation {
+        offset: usize,
+        error: spirv::ValidationError,
+    },
+    /// Reading a module or communicating with a sandbox helper failed.
+    Io {
+        #[cfg_attr(feature = "serde", serde(with = "io_error_kind"))]
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    /// The sandbox helper aborted. Contains what the helper wrote to stderr.
+    SandboxAborted(String),
+    /// The sandbox helper exited abnormally for a reason other than aborting.
+    SandboxCrashed(String),
+    /// The sandbox helper was killed after exceeding its timeout.
+    SandboxTimedOut,
+    /// The name does not match any target enabled in this build.
+    UnknownTarget(String),
+    /// Options for one target were given to a compiler for another.
+    TargetMismatch {
+        expected: backend::TargetKind,
+        found: backend::TargetKind,
+    },
+}
+
+impl ErrorCode {
+    /// Compiler errors raised while constructing a compiler are caused by the module itself.
+    pub(crate) fn into_invalid_module(self) -> Self {
+        match self {
+            ErrorCode::CompilationError(message) => ErrorCode::InvalidModule(message),
+            error => error,
+        }
+    }
+
+    pub(crate) fn from_io(error: std::io::Error) -> Self {
+        ErrorCode::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// Recovers a `'static` kind name for `ErrorCode::UnsupportedEnumValue`. Each distinct name is
+/// leaked once, and there are only as many as there are enums converted from SPIRV-Cross.
+#[cfg(feature = "serde")]
+mod enum_kind {
+    use serde::{Deserialize, Deserializer};
+    use std::sync::Mutex;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<&'static str, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        static KINDS: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+        let kind = String::deserialize(deserializer)?;
+        let mut kinds = KINDS.lock().unwrap_or_else(|error| error.into_inner());
+        Ok(match kinds.iter().find(|known| **known == kind) {
+            Some(known) => known,
+            None => {
+                let kind = Box::leak(kind.into_boxed_str());
+                kinds.push(kind);
+                kind
+            }
+        })
+    }
+}
+
+/// `io::ErrorKind` has no serde support of its own, so kinds are stored as their index in
+/// `KINDS`. The enum is non-exhaustive, and kinds added to `std` after this list was written are
+/// stored as `Other`. The message is always stored intact.
+#[cfg(feature = "serde")]
+mod io_error_kind {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::io::ErrorKind;
+
+    const KINDS: &[ErrorKind] = &[
+        ErrorKind::Other,
+        ErrorKind::NotFound,
+        ErrorKind::PermissionDenied,
+        ErrorKind::ConnectionRefused,
+        ErrorKind::ConnectionReset,
+        ErrorKind::ConnectionAborted,
+        ErrorKind::NotConnected,
+        ErrorKind::AddrInUse,
+        ErrorKind::AddrNotAvailable,
+        ErrorKind::BrokenPipe,
+        ErrorKind::AlreadyExists,
+        ErrorKind::WouldBlock,
+        ErrorKind::InvalidInput,
+        ErrorKind::InvalidData,
+        ErrorKind::TimedOut,
+        ErrorKind::WriteZero,
+        ErrorKind::Interrupted,
+        ErrorKind::Unsupported,
+        ErrorKind::UnexpectedEof,
+        ErrorKind::OutOfMemory,
+    ];
+
+    pub fn serialize<S: Serializer>(kind: &ErrorKind, serializer: S) -> Result<S::Ok, S::Error> {
+        let index = KINDS.iter().position(|known| known == kind);
+        (index.unwrap_or(0) as u32).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ErrorKind, D::Error> {
+        let index = u32::deserialize(deserializer)?;
+        Ok(KINDS
+            .get(index as usize)
+            .copied()
+            .unwrap_or(ErrorKind::Other))
+    }
 }
 
 impl std::fmt::Display for ErrorCode {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            ErrorCode::Unhandled => write!(f, "unhandled error"),
+            ErrorCode::CompilationError(message) => write!(f, "compilation error: {}", message),
+            ErrorCode::InvalidModule(message) => write!(f, "invalid SPIR-V module: {}", message),
+            ErrorCode::UnsupportedEnumValue { kind, raw } => {
+                write!(f, "unsupported {} value: {}", kind, raw)
+            }
+            ErrorCode::InvalidString => write!(
+                f,
+                "string contains an interior NUL byte or is not valid UTF-8"
+            ),
+            ErrorCode::NotCompiled => write!(f, "`compile` must be called first"),
+            ErrorCode::Backend(message) => write!(f, "backend error: {}", message),
+            ErrorCode::InvalidHeader(error) => write!(f, "invalid SPIR-V header: {}", error),
+            ErrorCode::Validation { offset, error } => {
+                write!(f, "invalid SPIR-V at word {}: {}", offset, error)
+            }
+            ErrorCode::Io { message, .. } => write!(f, "I/O error: {}", message),
+            ErrorCode::SandboxAborted(message) => {
+                write!(f, "sandbox helper aborted: {}", message)
+            }
+            ErrorCode::SandboxCrashed(message) => {
+                write!(f, "sandbox helper crashed: {}", message)
+            }
+            ErrorCode::SandboxTimedOut => write!(f, "sandbox helper timed out"),
+            ErrorCode::UnknownTarget(name) => write!(f, "unknown target: {}", name),
+            ErrorCode::TargetMismatch { expected, found } => write!(
+                f,
+                "{} compiler options cannot be used with a {} compiler",
+                found, expected
+            ),
+        }
     }
 }
 
 impl std::error::Error for ErrorCode {}
+
+/// Converts the result of a wrapper call into an `ErrorCode`, taking ownership of the error
+/// message left behind by the failing call.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) unsafe fn check_result(result: bindings::ScInternalResult) -> Result<(), ErrorCode> {
+    use crate::bindings as br;
+
+    if br::ScInternalResult::Success == result {
+        return Ok(());
+    }
+
+    let mut message_ptr = std::ptr::null();
+    if br::ScInternalResult::Success
+        != br::sc_internal_get_latest_exception_message(&mut message_ptr)
+    {
+        return Err(ErrorCode::Unhandled);
+    }
+
+    let message = if message_ptr.is_null() {
+        None
+    } else {
+        let message = std::ffi::CStr::from_ptr(message_ptr)
+            .to_string_lossy()
+            .into_owned();
+        if br::ScInternalResult::Success
+            != br::sc_internal_free_pointer(message_ptr as *mut std::os::raw::c_void)
+        {
+            return Err(ErrorCode::Unhandled);
+        }
+        Some(message)
+    };
+
+    Err(match (result, message) {
+        (br::ScInternalResult::CompilationError, Some(message)) => {
+            ErrorCode::CompilationError(message)
+        }
+        (br::ScInternalResult::Unhandled, Some(message)) => ErrorCode::Backend(message),
+        _ => ErrorCode::Unhandled,
+    })
+}
+
+/// Errors aren't propagated from the Emscripten bindings.
+#[cfg(target_arch = "wasm32")]
+pub(crate) unsafe fn check_result(_result: bindings::ScInternalResult) -> Result<(), ErrorCode> {
+    Ok(())
+}
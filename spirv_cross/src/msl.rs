@@ -11,6 +11,7 @@ use std::u8;
 #[derive(Debug, Clone)]
 pub enum Target {}
 
+#[derive(Clone)]
 pub struct TargetData {
     vertex_attribute_overrides: Vec<br::spirv_cross::MSLShaderInput>,
     resource_binding_overrides: Vec<br::spirv_cross::MSLResourceBinding>,
@@ -23,10 +24,12 @@ impl spirv::Target for Target {
 
 /// Location of a vertex attribute to override
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexAttributeLocation(pub u32);
 
 /// Format of the vertex attribute
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Format {
     Other,
     Uint8,
@@ -46,6 +49,7 @@ impl Format {
 
 /// Vertex attribute description for overriding
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexAttribute {
     pub buffer_id: u32,
     pub format: Format,
@@ -55,6 +59,7 @@ pub struct VertexAttribute {
 
 /// Location of a resource binding to override
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResourceBindingLocation {
     pub stage: spirv::ExecutionModel,
     pub desc_set: u32,
@@ -63,6 +68,7 @@ pub struct ResourceBindingLocation {
 
 /// Resource binding description for overriding
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ResourceBinding {
     pub buffer_id: u32,
     pub texture_id: u32,
@@ -72,6 +78,7 @@ pub struct ResourceBinding {
 
 /// Location of a sampler binding to override
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SamplerLocation {
     pub desc_set: u32,
     pub binding: u32,
@@ -79,6 +86,7 @@ pub struct SamplerLocation {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerCoord {
     Normalized = 0,
     Pixel = 1,
@@ -86,6 +94,7 @@ pub enum SamplerCoord {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerFilter {
     Nearest = 0,
     Linear = 1,
@@ -93,6 +102,7 @@ pub enum SamplerFilter {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerMipFilter {
     None = 0,
     Nearest = 1,
@@ -101,6 +111,7 @@ pub enum SamplerMipFilter {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerAddress {
     ClampToZero = 0,
     ClampToEdge = 1,
@@ -111,6 +122,7 @@ pub enum SamplerAddress {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerCompareFunc {
     Never = 0,
     Less = 1,
@@ -124,6 +136,7 @@ pub enum SamplerCompareFunc {
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerBorderColor {
     TransparentBlack = 0,
     OpaqueBlack = 1,
@@ -132,7 +145,9 @@ pub enum SamplerBorderColor {
 
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
-pub struct LodBase16(u8);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "f32", into = "f32"))]
+pub struct LodBase16(pub(crate) u8);
 
 impl LodBase16 {
     pub const ZERO: Self = LodBase16(0);
@@ -154,6 +169,7 @@ impl Into<f32> for LodBase16 {
 /// MSL format resolution.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FormatResolution {
     _444 = 0,
     _422 = 1,
@@ -163,6 +179,7 @@ pub enum FormatResolution {
 /// MSL chroma location.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChromaLocation {
     CositedEven = 0,
     LocationMidpoint = 1,
@@ -171,6 +188,7 @@ pub enum ChromaLocation {
 /// MSL component swizzle.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ComponentSwizzle {
     Identity = 0,
     Zero = 1,
@@ -183,6 +201,8 @@ pub enum ComponentSwizzle {
 
 /// Data fully defining a constant sampler.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct SamplerData {
     pub coord: SamplerCoord,
     pub min_filter: SamplerFilter,
@@ -209,9 +229,40 @@ pub struct SamplerData {
     pub bpc: u32,
 }
 
+/// The defaults of SPIRV-Cross' `MSLConstexprSampler`.
+impl Default for SamplerData {
+    fn default() -> Self {
+        SamplerData {
+            coord: SamplerCoord::Normalized,
+            min_filter: SamplerFilter::Nearest,
+            mag_filter: SamplerFilter::Nearest,
+            mip_filter: SamplerMipFilter::None,
+            s_address: SamplerAddress::ClampToEdge,
+            t_address: SamplerAddress::ClampToEdge,
+            r_address: SamplerAddress::ClampToEdge,
+            compare_func: SamplerCompareFunc::Never,
+            border_color: SamplerBorderColor::TransparentBlack,
+            lod_clamp_min: LodBase16::ZERO,
+            lod_clamp_max: LodBase16::MAX,
+            max_anisotropy: 1,
+            planes: 0,
+            resolution: FormatResolution::_444,
+            chroma_filter: SamplerFilter::Nearest,
+            x_chroma_offset: ChromaLocation::CositedEven,
+            y_chroma_offset: ChromaLocation::CositedEven,
+            swizzle: [ComponentSwizzle::Identity; 4],
+            ycbcr_conversion_enable: false,
+            ycbcr_model: SamplerYCbCrModelConversion::RgbIdentity,
+            ycbcr_range: SamplerYCbCrRange::ItuFull,
+            bpc: 8,
+        }
+    }
+}
+
 /// A MSL sampler YCbCr model conversion.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerYCbCrModelConversion {
     RgbIdentity = 0,
     YCbCrIdentity = 1,
@@ -223,6 +274,7 @@ pub enum SamplerYCbCrModelConversion {
 /// A MSL sampler YCbCr range.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerYCbCrRange {
     ItuFull = 0,
     ItuNarrow = 1,
@@ -232,6 +284,7 @@ pub enum SamplerYCbCrRange {
 #[repr(u8)]
 #[allow(non_snake_case, non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Platform {
     iOS = 0,
     macOS = 1,
@@ -239,6 +292,7 @@ pub enum Platform {
 
 /// A MSL shader model version.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Version {
     V1_0,
@@ -266,6 +320,8 @@ impl Version {
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerVertexOptions {
     pub invert_y: bool,
     pub transform_clip_space: bool,
@@ -283,6 +339,8 @@ impl Default for CompilerVertexOptions {
 /// MSL compiler options.
 #[non_exhaustive]
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct CompilerOptions {
     /// The target platform.
     pub platform: Platform,
@@ -317,10 +375,13 @@ pub struct CompilerOptions {
     /// Whether to pad fragment output to have at least the number of components as the render pass.
     pub pad_fragment_output_components: bool,
     /// MSL resource bindings overrides.
+    #[cfg_attr(feature = "serde", serde(with = "entries"))]
     pub resource_binding_overrides: BTreeMap<ResourceBindingLocation, ResourceBinding>,
     /// MSL vertex attribute overrides.
+    #[cfg_attr(feature = "serde", serde(with = "entries"))]
     pub vertex_attribute_overrides: BTreeMap<VertexAttributeLocation, VertexAttribute>,
     /// MSL const sampler mappings.
+    #[cfg_attr(feature = "serde", serde(with = "entries"))]
     pub const_samplers: BTreeMap<SamplerLocation, SamplerData>,
     /// Whether to force native arrays (useful to workaround issues on some hardware).
     pub force_native_arrays: bool,
@@ -363,29 +424,78 @@ impl Default for CompilerOptions {
     }
 }
 
+/// Serializes the override maps as lists of `(location, value)` pairs, since their keys are not
+/// strings and formats such as JSON only allow string keys.
+#[cfg(feature = "serde")]
+mod entries {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Ord,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
 impl<'a> spirv::Parse<Target> for spirv::Ast<Target> {
     fn parse(module: &spirv::Module) -> Result<Self, ErrorCode> {
+        module.validate_if_requested()?;
         let mut sc_compiler = ptr::null_mut();
         unsafe {
-            check!(br::sc_internal_compiler_msl_new(
+            crate::check_result(br::sc_internal_compiler_msl_new(
                 &mut sc_compiler,
                 module.words.as_ptr(),
                 module.words.len(),
-            ));
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
         }
 
-        Ok(spirv::Ast {
-            compiler: compiler::Compiler {
-                sc_compiler,
-                target_data: TargetData {
-                    resource_binding_overrides: Vec::new(),
-                    vertex_attribute_overrides: Vec::new(),
-                    const_samplers: Vec::new(),
-                },
-                has_been_compiled: false,
+        Ok(new_ast(sc_compiler))
+    }
+}
+
+impl spirv::FromParsedModule<Target> for spirv::Ast<Target> {
+    fn from_parsed_module(module: &spirv::ParsedModule) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            crate::check_result(br::sc_internal_compiler_msl_new_from_parsed_ir(
+                &mut sc_compiler,
+                module.ir,
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+
+        Ok(new_ast(sc_compiler))
+    }
+}
+
+fn new_ast(sc_compiler: *mut br::ScInternalCompilerMsl) -> spirv::Ast<Target> {
+    spirv::Ast {
+        compiler: compiler::Compiler {
+            sc_compiler,
+            target_data: TargetData {
+                resource_binding_overrides: Vec::new(),
+                vertex_attribute_overrides: Vec::new(),
+                const_samplers: Vec::new(),
             },
-            target_type: PhantomData,
-        })
+            has_been_compiled: false,
+        },
+        target_type: PhantomData,
     }
 }
 
@@ -395,7 +505,7 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
     /// Set MSL compiler specific compilation settings.
     fn set_compiler_options(&mut self, options: &CompilerOptions) -> Result<(), ErrorCode> {
         if let Some((name, model)) = &options.entry_point {
-            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::Unhandled)?;
+            let name_raw = CString::new(name.as_str()).map_err(|_| ErrorCode::InvalidString)?;
             let model = model.as_raw();
             unsafe {
                 check!(br::sc_internal_compiler_set_entry_point(
@@ -511,6 +621,27 @@ impl spirv::Compile<Target> for spirv::Ast<Target> {
 }
 
 impl spirv::Ast<Target> {
+    /// Creates a deep copy of this `Ast`, including its renames, decorations, specialization
+    /// constants and options, which can then be configured and compiled independently.
+    pub fn try_clone(&self) -> Result<Self, ErrorCode> {
+        let mut sc_compiler = ptr::null_mut();
+        unsafe {
+            check!(br::sc_internal_compiler_msl_clone(
+                &mut sc_compiler,
+                self.compiler.sc_compiler,
+            ));
+        }
+
+        Ok(spirv::Ast {
+            compiler: compiler::Compiler {
+                sc_compiler,
+                target_data: self.compiler.target_data.clone(),
+                has_been_compiled: self.compiler.has_been_compiled,
+            },
+            target_type: PhantomData,
+        })
+    }
+
     fn compile_internal(&self) -> Result<String, ErrorCode> {
         let vat_overrides = &self.compiler.target_data.vertex_attribute_overrides;
         let res_overrides = &self.compiler.target_data.resource_binding_overrides;
@@ -529,7 +660,7 @@ impl spirv::Ast<Target> {
             ));
             let shader = match CStr::from_ptr(shader_ptr).to_str() {
                 Ok(v) => v.to_owned(),
-                Err(_) => return Err(ErrorCode::Unhandled),
+                Err(_) => return Err(ErrorCode::InvalidString),
             };
             check!(br::sc_internal_free_pointer(
                 shader_ptr as *mut std::os::raw::c_void
@@ -16,7 +16,7 @@ pub unsafe fn read_string_from_ptr(ptr: *const std::os::raw::c_char) -> Result<S
     let string = CStr::from_ptr(ptr)
         .to_owned()
         .into_string()
-        .map_err(|_| ErrorCode::Unhandled);
+        .map_err(|_| ErrorCode::InvalidString);
     #[cfg(target_arch = "wasm32")]
     let string = {
         let bytes = emscripten::get_module().read_bytes_into_vec_while(
@@ -24,7 +24,7 @@ pub unsafe fn read_string_from_ptr(ptr: *const std::os::raw::c_char) -> Result<S
             |byte, _| 0 != byte,
             false,
         );
-        String::from_utf8(bytes).map_err(|_| ErrorCode::Unhandled)
+        String::from_utf8(bytes).map_err(|_| ErrorCode::InvalidString)
     };
     string
 }
@@ -51,6 +51,11 @@ pub unsafe fn read_from_ptr<T>(ptr: *const T) -> T {
 }
 
 pub unsafe fn read_into_vec_from_ptr<T: Clone>(ptr: *const T, size: usize) -> Vec<T> {
+    // Empty arrays may be returned as a null pointer, such as by `malloc(0)`, which
+    // `slice::from_raw_parts` doesn't accept.
+    if size == 0 {
+        return Vec::new();
+    }
     #[cfg(not(target_arch = "wasm32"))]
     let values = slice::from_raw_parts(ptr, size).to_vec();
     #[cfg(target_arch = "wasm32")]
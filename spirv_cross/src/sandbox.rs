@@ -0,0 +1,231 @@
+//! Compiles modules in a helper process, so that SPIRV-Cross crashing, aborting or hanging on a
+//! module is reported as an `ErrorCode` instead of taking down the host process.
+//!
+//! The helper is the `spirv-cross-sandbox` binary built from this crate with the `sandbox`
+//! feature. Custom helpers can call `run_helper` from their `main` function.
+
+use crate::backend::{AnyAst, AnyCompilerOptions};
+use crate::spirv::{CompiledShader, Module};
+use crate::ErrorCode;
+use std::ffi::OsString;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Identifies the build which wrote a message. Requests and responses are encoded with bincode
+/// from the serde derives of the exchanged types, so both processes must be built from the same
+/// version of this crate with the same targets enabled.
+const FORMAT_VERSION: &str = concat!("spirv_cross ", env!("CARGO_PKG_VERSION"));
+
+/// Runs compilations in a helper process.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    helper: PathBuf,
+    args: Vec<OsString>,
+    timeout: Duration,
+}
+
+impl Sandbox {
+    /// The default time a compilation may take before the helper is killed.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Creates a sandbox which runs the helper binary at `helper`.
+    pub fn new<P: Into<PathBuf>>(helper: P) -> Self {
+        Sandbox {
+            helper: helper.into(),
+            args: Vec::new(),
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the arguments passed to the helper.
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the time a compilation may take before the helper is killed.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Parses and compiles `module` in a new helper process.
+    ///
+    /// Returns `ErrorCode::SandboxAborted`, `ErrorCode::SandboxCrashed` or
+    /// `ErrorCode::SandboxTimedOut` if the helper does not exit normally.
+    pub fn compile(
+        &self,
+        module: &Module,
+        options: &AnyCompilerOptions,
+    ) -> Result<CompiledShader, ErrorCode> {
+        module.validate_if_requested()?;
+
+        let request = encode(&(FORMAT_VERSION, options, module.words))?;
+
+        let mut child = Command::new(&self.helper)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(ErrorCode::from_io)?;
+
+        // The pipes are serviced on their own threads so that a helper which stops reading or
+        // fills its output pipe can't block the host past the timeout.
+        let mut stdin = child.stdin.take().unwrap();
+        let writer = thread::spawn(move || {
+            // The helper may exit without reading the request, which is reported by its status.
+            let _ = stdin.write_all(&request);
+        });
+        let stdout = read_on_thread(child.stdout.take().unwrap());
+        let stderr = read_on_thread(child.stderr.take().unwrap());
+
+        let status = wait_timeout(&mut child, self.timeout).map_err(ErrorCode::from_io)?;
+        let _ = writer.join();
+        let status = match status {
+            Some(status) => status,
+            None => return Err(ErrorCode::SandboxTimedOut),
+        };
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        let stderr = String::from_utf8_lossy(&stderr).trim().to_owned();
+
+        if !status.success() {
+            return Err(if is_abort(status) {
+                ErrorCode::SandboxAborted(stderr)
+            } else if stderr.is_empty() {
+                ErrorCode::SandboxCrashed(status.to_string())
+            } else {
+                ErrorCode::SandboxCrashed(format!("{}: {}", status, stderr))
+            });
+        }
+
+        if format_version(&stdout)? != FORMAT_VERSION {
+            return Err(malformed());
+        }
+        let (_, result) = decode::<(String, Result<CompiledShader, ErrorCode>)>(&stdout)?;
+        result
+    }
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, ErrorCode> {
+    bincode::serialize(value).map_err(|error| ErrorCode::Backend(error.to_string()))
+}
+
+/// Decodes a whole message, failing if any bytes are left over.
+fn decode<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, ErrorCode> {
+    use bincode::Options;
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+        .deserialize(bytes)
+        .map_err(|_| malformed())
+}
+
+/// Decodes only the `FORMAT_VERSION` at the start of a message, which is checked before the rest
+/// of the message is decoded.
+fn format_version(bytes: &[u8]) -> Result<String, ErrorCode> {
+    bincode::deserialize(bytes).map_err(|_| malformed())
+}
+
+fn malformed() -> ErrorCode {
+    ErrorCode::Backend(String::from("malformed message from another process"))
+}
+
+fn read_on_thread<R: Read + Send + 'static>(mut reader: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = reader.read_to_end(&mut bytes);
+        bytes
+    })
+}
+
+/// Waits for `child` to exit, killing it if it takes longer than `timeout`.
+fn wait_timeout(child: &mut Child, timeout: Duration) -> std::io::Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            // The helper may exit between `try_wait` and `kill`, which is still a timeout.
+            let _ = child.kill();
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(Duration::from_millis(50));
+    }
+}
+
+#[cfg(unix)]
+fn is_abort(status: ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    // SIGABRT
+    status.signal() == Some(6)
+}
+
+#[cfg(windows)]
+fn is_abort(status: ExitStatus) -> bool {
+    // The exit code of `abort` in the Microsoft C runtime.
+    status.code() == Some(3)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn is_abort(_status: ExitStatus) -> bool {
+    false
+}
+
+/// Serves a single request from `Sandbox::compile`, reading it from stdin and writing the
+/// response to stdout.
+///
+/// Errors raised while compiling are part of the response. Only failing to read the request or
+/// write the response is returned.
+pub fn run_helper() -> Result<(), ErrorCode> {
+    let mut request = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut request)
+        .map_err(ErrorCode::from_io)?;
+
+    let result = serve(&request);
+
+    let response = encode(&(FORMAT_VERSION, result))?;
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    stdout
+        .write_all(&response)
+        .and_then(|()| stdout.flush())
+        .map_err(ErrorCode::from_io)
+}
+
+#[cfg_attr(
+    not(any(
+        feature = "glsl",
+        feature = "hlsl",
+        feature = "msl",
+        feature = "cpp",
+        feature = "reflect"
+    )),
+    allow(unused_variables, unreachable_code)
+)]
+fn serve(request: &[u8]) -> Result<CompiledShader, ErrorCode> {
+    if format_version(request)? != FORMAT_VERSION {
+        return Err(ErrorCode::Backend(String::from(
+            "sandbox helper was built from a different version of spirv_cross",
+        )));
+    }
+    let (_, options, words) = decode::<(String, AnyCompilerOptions, Vec<u32>)>(request)?;
+
+    let mut ast = AnyAst::parse(options.kind(), &Module::from_words(&words))?;
+    ast.set_compiler_options(&options)?;
+    ast.compile_and_reflect()
+}
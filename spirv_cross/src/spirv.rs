@@ -1,9 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use crate::{compiler, ErrorCode};
 use std::marker::PhantomData;
 
+pub use crate::validation::ValidationError;
+
 /// A stage or compute kernel.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CombinedImageSampler {
     pub combined_id: u32,
     pub image_id: u32,
@@ -12,6 +15,7 @@ pub struct CombinedImageSampler {
 
 /// A stage or compute kernel.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExecutionModel {
     Vertex,
     TessellationControl,
@@ -20,67 +24,207 @@ pub enum ExecutionModel {
     Fragment,
     GlCompute,
     Kernel,
+    TaskNv,
+    MeshNv,
+    RayGeneration,
+    Intersection,
+    AnyHit,
+    ClosestHit,
+    Miss,
+    Callable,
 }
 
 /// A decoration.
+///
+/// The discriminant of each variant is its value in SPIR-V, so `decoration as u32` gives the raw
+/// value and `Decoration::from_raw` converts it back.
+#[repr(u32)]
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum Decoration {
-    RelaxedPrecision,
-    SpecId,
-    Block,
-    BufferBlock,
-    RowMajor,
-    ColMajor,
-    ArrayStride,
-    MatrixStride,
-    GlslShared,
-    GlslPacked,
-    CPacked,
-    BuiltIn,
-    NoPerspective,
-    Flat,
-    Patch,
-    Centroid,
-    Sample,
-    Invariant,
-    Restrict,
-    Aliased,
-    Volatile,
-    Constant,
-    Coherent,
-    NonWritable,
-    NonReadable,
-    Uniform,
-    SaturatedConversion,
-    Stream,
-    Location,
-    Component,
-    Index,
-    Binding,
-    DescriptorSet,
-    Offset,
-    XfbBuffer,
-    XfbStride,
-    FuncParamAttr,
-    FpRoundingMode,
-    FpFastMathMode,
-    LinkageAttributes,
-    NoContraction,
-    InputAttachmentIndex,
-    Alignment,
-    OverrideCoverageNv,
-    PassthroughNv,
-    ViewportRelativeNv,
-    SecondaryViewportRelativeNv,
+    RelaxedPrecision = 0,
+    SpecId = 1,
+    Block = 2,
+    BufferBlock = 3,
+    RowMajor = 4,
+    ColMajor = 5,
+    ArrayStride = 6,
+    MatrixStride = 7,
+    GlslShared = 8,
+    GlslPacked = 9,
+    CPacked = 10,
+    BuiltIn = 11,
+    NoPerspective = 13,
+    Flat = 14,
+    Patch = 15,
+    Centroid = 16,
+    Sample = 17,
+    Invariant = 18,
+    Restrict = 19,
+    Aliased = 20,
+    Volatile = 21,
+    Constant = 22,
+    Coherent = 23,
+    NonWritable = 24,
+    NonReadable = 25,
+    Uniform = 26,
+    UniformId = 27,
+    SaturatedConversion = 28,
+    Stream = 29,
+    Location = 30,
+    Component = 31,
+    Index = 32,
+    Binding = 33,
+    DescriptorSet = 34,
+    Offset = 35,
+    XfbBuffer = 36,
+    XfbStride = 37,
+    FuncParamAttr = 38,
+    FpRoundingMode = 39,
+    FpFastMathMode = 40,
+    LinkageAttributes = 41,
+    NoContraction = 42,
+    InputAttachmentIndex = 43,
+    Alignment = 44,
+    MaxByteOffset = 45,
+    AlignmentId = 46,
+    MaxByteOffsetId = 47,
+    NoSignedWrap = 4469,
+    NoUnsignedWrap = 4470,
+    ExplicitInterpAmd = 4999,
+    OverrideCoverageNv = 5248,
+    PassthroughNv = 5250,
+    ViewportRelativeNv = 5252,
+    SecondaryViewportRelativeNv = 5256,
+    PerPrimitiveNv = 5271,
+    PerViewNv = 5272,
+    PerTaskNv = 5273,
+    PerVertexNv = 5285,
+    NonUniform = 5300,
+    RestrictPointer = 5355,
+    AliasedPointer = 5356,
+    ReferencedIndirectlyIntel = 5602,
+    /// Also known as `CounterBuffer`. Its argument is the ID of the counter buffer.
+    HlslCounterBuffer = 5634,
+    /// Also known as `HlslSemanticGOOGLE`. A string decoration, see `Ast::get_decoration_string`.
+    UserSemantic = 5635,
+    UserTypeGoogle = 5636,
+    RegisterIntel = 5825,
+    MemoryIntel = 5826,
+    NumbanksIntel = 5827,
+    BankwidthIntel = 5828,
+    MaxPrivateCopiesIntel = 5829,
+    SinglepumpIntel = 5830,
+    DoublepumpIntel = 5831,
+    MaxReplicatesIntel = 5832,
+    SimpleDualPortIntel = 5833,
+    MergeIntel = 5834,
+    BankBitsIntel = 5835,
+    ForcePow2DepthIntel = 5836,
+}
+
+impl Decoration {
+    /// Converts a raw SPIR-V decoration value.
+    pub fn from_raw(raw: u32) -> Result<Self, ErrorCode> {
+        use self::Decoration::*;
+        Ok(match raw {
+            0 => RelaxedPrecision,
+            1 => SpecId,
+            2 => Block,
+            3 => BufferBlock,
+            4 => RowMajor,
+            5 => ColMajor,
+            6 => ArrayStride,
+            7 => MatrixStride,
+            8 => GlslShared,
+            9 => GlslPacked,
+            10 => CPacked,
+            11 => BuiltIn,
+            13 => NoPerspective,
+            14 => Flat,
+            15 => Patch,
+            16 => Centroid,
+            17 => Sample,
+            18 => Invariant,
+            19 => Restrict,
+            20 => Aliased,
+            21 => Volatile,
+            22 => Constant,
+            23 => Coherent,
+            24 => NonWritable,
+            25 => NonReadable,
+            26 => Uniform,
+            27 => UniformId,
+            28 => SaturatedConversion,
+            29 => Stream,
+            30 => Location,
+            31 => Component,
+            32 => Index,
+            33 => Binding,
+            34 => DescriptorSet,
+            35 => Offset,
+            36 => XfbBuffer,
+            37 => XfbStride,
+            38 => FuncParamAttr,
+            39 => FpRoundingMode,
+            40 => FpFastMathMode,
+            41 => LinkageAttributes,
+            42 => NoContraction,
+            43 => InputAttachmentIndex,
+            44 => Alignment,
+            45 => MaxByteOffset,
+            46 => AlignmentId,
+            47 => MaxByteOffsetId,
+            4469 => NoSignedWrap,
+            4470 => NoUnsignedWrap,
+            4999 => ExplicitInterpAmd,
+            5248 => OverrideCoverageNv,
+            5250 => PassthroughNv,
+            5252 => ViewportRelativeNv,
+            5256 => SecondaryViewportRelativeNv,
+            5271 => PerPrimitiveNv,
+            5272 => PerViewNv,
+            5273 => PerTaskNv,
+            5285 => PerVertexNv,
+            5300 => NonUniform,
+            5355 => RestrictPointer,
+            5356 => AliasedPointer,
+            5602 => ReferencedIndirectlyIntel,
+            5634 => HlslCounterBuffer,
+            5635 => UserSemantic,
+            5636 => UserTypeGoogle,
+            5825 => RegisterIntel,
+            5826 => MemoryIntel,
+            5827 => NumbanksIntel,
+            5828 => BankwidthIntel,
+            5829 => MaxPrivateCopiesIntel,
+            5830 => SinglepumpIntel,
+            5831 => DoublepumpIntel,
+            5832 => MaxReplicatesIntel,
+            5833 => SimpleDualPortIntel,
+            5834 => MergeIntel,
+            5835 => BankBitsIntel,
+            5836 => ForcePow2DepthIntel,
+            _ => {
+                return Err(ErrorCode::UnsupportedEnumValue {
+                    kind: "Decoration",
+                    raw,
+                })
+            }
+        })
+    }
 }
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VertexAttributeStep {
     Vertex,
     Instance,
 }
 
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BuiltIn {
     Position,
     PointSize,
@@ -277,6 +421,7 @@ pub(crate) fn built_in_as_raw(built_in: Option<BuiltIn>) -> crate::bindings::spv
 
 /// A work group size.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorkGroupSize {
     pub x: u32,
     pub y: u32,
@@ -285,14 +430,347 @@ pub struct WorkGroupSize {
 
 /// An entry point for a SPIR-V module.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntryPoint {
     pub name: String,
     pub execution_model: ExecutionModel,
     pub work_group_size: WorkGroupSize,
 }
 
+/// The execution modes of an entry point, see `Ast::get_execution_modes`.
+///
+/// Modes which don't apply to the entry point's execution model are left unset.
+#[non_exhaustive]
+#[derive(Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecutionModes {
+    /// The origin of fragment coordinates.
+    pub origin: Option<Origin>,
+    /// Whether fragment tests are performed before the fragment shader runs.
+    pub early_fragment_tests: bool,
+    /// Whether the fragment shader writes the fragment depth.
+    pub depth_replacing: bool,
+    /// How the fragment shader changes the fragment depth, if it writes it.
+    pub depth: Option<DepthMode>,
+    /// The primitive a geometry shader reads, or the domain a tessellation shader subdivides.
+    pub input_primitive: Option<InputPrimitive>,
+    /// The primitive a geometry shader emits.
+    pub output_primitive: Option<OutputPrimitive>,
+    /// The maximum number of vertices a geometry shader emits, or the number of vertices in the
+    /// output patch of a tessellation control shader.
+    pub output_vertices: Option<u32>,
+    /// The number of times a geometry shader is invoked for each input primitive.
+    pub invocations: Option<u32>,
+    /// How tessellated edges are segmented.
+    pub spacing: Option<TessellationSpacing>,
+    /// The winding order of the primitives the tessellator generates.
+    pub winding: Option<Winding>,
+    /// The ids of the constants which give the work group size, set by `LocalSizeId` instead of a
+    /// literal `LocalSize`.
+    pub local_size_id: Option<LocalSizeId>,
+    /// The fragment shader interlock ordering, from `SPV_EXT_fragment_shader_interlock`.
+    pub interlock: Option<Interlock>,
+}
+
+/// The origin of fragment coordinates.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Origin {
+    UpperLeft,
+    LowerLeft,
+}
+
+/// How a fragment shader changes the fragment depth.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DepthMode {
+    Greater,
+    Less,
+    Unchanged,
+}
+
+/// The primitive a geometry shader reads, or the domain a tessellation shader subdivides.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InputPrimitive {
+    Points,
+    Lines,
+    LinesAdjacency,
+    Triangles,
+    TrianglesAdjacency,
+    Quads,
+    Isolines,
+}
+
+/// The primitive a geometry shader emits.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutputPrimitive {
+    Points,
+    LineStrip,
+    TriangleStrip,
+}
+
+/// How tessellated edges are segmented.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TessellationSpacing {
+    Equal,
+    FractionalEven,
+    FractionalOdd,
+}
+
+/// The winding order of tessellated primitives.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// The ids of the constants which give a work group size.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocalSizeId {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+/// A fragment shader interlock ordering.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interlock {
+    PixelOrdered,
+    PixelUnordered,
+    SampleOrdered,
+    SampleUnordered,
+    ShadingRateOrdered,
+    ShadingRateUnordered,
+}
+
+/// Declared layout of a struct type, see `Ast::get_struct_layout`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructLayout {
+    pub type_id: u32,
+    /// Size in bytes, to which a trailing runtime array contributes nothing.
+    pub size: u32,
+    pub members: Vec<StructMemberLayout>,
+}
+
+/// Declared layout of a struct member.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructMemberLayout {
+    pub index: u32,
+    pub name: String,
+    pub type_id: u32,
+    /// Bytes from the start of the struct.
+    pub offset: u32,
+    /// Size in bytes, which is 0 for runtime arrays.
+    pub size: u32,
+    /// Array dimensions, innermost first as in `Type`. Runtime arrays have a dimension of 0.
+    pub array: Vec<u32>,
+    pub array_size_literal: Vec<bool>,
+    /// `ArrayStride` of each dimension in `array`.
+    pub array_strides: Vec<u32>,
+    /// Set for matrices and arrays of matrices.
+    pub matrix: Option<MatrixLayout>,
+    /// Set for structs and arrays of structs, but not for pointers to structs.
+    pub struct_layout: Option<StructLayout>,
+}
+
+/// Declared layout of a matrix struct member.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatrixLayout {
+    /// Bytes between the start of consecutive columns, or rows if `row_major` is set.
+    pub stride: u32,
+    pub row_major: bool,
+}
+
+/// A Rust type whose layout can be checked against a buffer block with
+/// `Ast::check_block_layout`.
+///
+/// With the `derive` feature this can be implemented with `#[derive(ShaderBlock)]`. Fields are
+/// matched to struct members by name, ignoring case and underscores, and fields starting with an
+/// underscore are treated as padding.
+pub trait ShaderBlock: Sized {
+    /// Describes the fields of the type.
+    fn fields() -> Vec<BlockField>;
+}
+
+#[cfg(feature = "derive")]
+pub use spirv_cross_derive::ShaderBlock;
+
+/// Layout of a field of a Rust type, see `ShaderBlock`.
+#[derive(Clone, Debug)]
+pub struct BlockField {
+    pub name: &'static str,
+    /// Bytes from the start of the type.
+    pub offset: usize,
+    /// Size in bytes.
+    pub size: usize,
+    /// Bytes between consecutive elements of each level of nested Rust arrays, innermost first.
+    pub array_strides: Vec<usize>,
+    /// Fields of the struct, or array element struct, held by this field.
+    pub fields: Option<fn() -> Vec<BlockField>>,
+}
+
+/// A difference between a Rust type and the declared layout of a block, see
+/// `Ast::check_block_layout`. Fields of nested structs are named by their path, such as
+/// `lights.radius`.
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum LayoutMismatch {
+    /// The Rust type is smaller than the block.
+    BlockSize { expected: u32, actual: usize },
+    /// No field corresponds to the struct member.
+    MissingField { member: String },
+    /// No struct member corresponds to the field.
+    UnexpectedField { field: String },
+    Offset {
+        field: String,
+        expected: u32,
+        actual: usize,
+    },
+    Size {
+        field: String,
+        expected: u32,
+        actual: usize,
+    },
+    /// The field has fewer array levels than the member has array dimensions.
+    ArrayDimensions {
+        field: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The stride of the array dimension at `dimension`, innermost first, differs.
+    ArrayStride {
+        field: String,
+        dimension: usize,
+        expected: u32,
+        actual: usize,
+    },
+    /// The stride of the array level holding the columns, or rows, of a matrix differs.
+    MatrixStride {
+        field: String,
+        expected: u32,
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            LayoutMismatch::BlockSize { expected, actual } => write!(
+                f,
+                "block is {} bytes but the Rust type is {} bytes",
+                expected, actual
+            ),
+            LayoutMismatch::MissingField { member } => {
+                write!(f, "member `{}` has no corresponding field", member)
+            }
+            LayoutMismatch::UnexpectedField { field } => {
+                write!(f, "field `{}` has no corresponding member", field)
+            }
+            LayoutMismatch::Offset {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` is at offset {} but the member is at offset {}",
+                field, actual, expected
+            ),
+            LayoutMismatch::Size {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` is {} bytes but the member is {} bytes",
+                field, actual, expected
+            ),
+            LayoutMismatch::ArrayDimensions {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` has {} array levels but the member has {} array dimensions",
+                field, actual, expected
+            ),
+            LayoutMismatch::ArrayStride {
+                field,
+                dimension,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` has a stride of {} in array dimension {} but the member has {}",
+                field, actual, dimension, expected
+            ),
+            LayoutMismatch::MatrixStride {
+                field,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "field `{}` has a matrix stride of {} but the member has {}",
+                field, actual, expected
+            ),
+        }
+    }
+}
+
+/// Rules for laying out the members of a buffer block.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockLayout {
+    /// Arrays and structs are aligned to 16 bytes, as required for uniform buffers.
+    Std140,
+    Std430,
+    /// Members are aligned to their components, as allowed by `VK_EXT_scalar_block_layout`.
+    Scalar,
+}
+
+/// Looks up the types laid out by `BlockLayout::struct_layout`.
+///
+/// Implemented by `Ast`, and by maps for types built without a module.
+pub trait TypeLookup {
+    fn get_type(&self, id: u32) -> Result<Type, ErrorCode>;
+
+    /// Gets the element type of an array type. Defaults to `id`, in which case nested struct
+    /// layouts are identified by the type id of the member.
+    fn get_array_element_type(&self, id: u32) -> Result<u32, ErrorCode> {
+        Ok(id)
+    }
+
+    /// Whether a matrix member is row-major. Defaults to column-major.
+    fn is_row_major(&self, _struct_id: u32, _index: u32) -> Result<bool, ErrorCode> {
+        Ok(false)
+    }
+
+    /// Gets the name of a struct member. Defaults to an empty name.
+    fn get_member_name(&self, _struct_id: u32, _index: u32) -> Result<String, ErrorCode> {
+        Ok(String::new())
+    }
+}
+
+impl TypeLookup for HashMap<u32, Type> {
+    fn get_type(&self, id: u32) -> Result<Type, ErrorCode> {
+        self.get(&id)
+            .cloned()
+            .ok_or_else(|| ErrorCode::CompilationError(format!("unknown type {}", id)))
+    }
+}
+
 /// Description of struct member's range.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BufferRange {
     /// An index. Useful for passing to `get_member_name` and `get_member_decoration`.
     pub index: u32,
@@ -304,6 +782,7 @@ pub struct BufferRange {
 
 /// A resource.
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Resource {
     pub id: u32,
     pub type_id: u32,
@@ -313,6 +792,7 @@ pub struct Resource {
 
 /// Specialization constant reference.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpecializationConstant {
     pub id: u32,
     pub constant_id: u32,
@@ -320,6 +800,7 @@ pub struct SpecializationConstant {
 
 /// Work group size specialization constants.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorkGroupSizeSpecializationConstants {
     pub x: SpecializationConstant,
     pub y: SpecializationConstant,
@@ -327,7 +808,8 @@ pub struct WorkGroupSizeSpecializationConstants {
 }
 
 /// Shader resources.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShaderResources {
     pub uniform_buffers: Vec<Resource>,
     pub storage_buffers: Vec<Resource>,
@@ -342,7 +824,18 @@ pub struct ShaderResources {
     pub separate_samplers: Vec<Resource>,
 }
 
+/// The output of a compilation together with the reflection data describing it.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CompiledShader {
+    pub source: String,
+    pub entry_points: Vec<EntryPoint>,
+    pub shader_resources: ShaderResources,
+    pub specialization_constants: Vec<SpecializationConstant>,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Dim {
     Dim1D = 0,
     Dim2D = 1,
@@ -354,6 +847,7 @@ pub enum Dim {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageFormat {
     Unknown = 0,
     Rgba32f = 1,
@@ -399,8 +893,19 @@ pub enum ImageFormat {
     R64i = 41,
 }
 
+/// Access qualifier of an OpenCL image.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccessQualifier {
+    ReadOnly = 0,
+    WriteOnly = 1,
+    ReadWrite = 2,
+}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ImageType {
+    /// The ID of the sampled type, which is the component type returned by image reads.
     pub type_id: u32,
     pub dim: Dim,
     pub depth: bool,
@@ -408,106 +913,196 @@ pub struct ImageType {
     pub ms: bool,
     pub sampled: u32,
     pub format: ImageFormat,
+    /// Only set for kernel images, which declare how they may be accessed.
+    pub access: Option<AccessQualifier>,
 }
 
+/// Storage class of a pointer.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum StorageClass {
+    UniformConstant = 0,
+    Input = 1,
+    Uniform = 2,
+    Output = 3,
+    Workgroup = 4,
+    CrossWorkgroup = 5,
+    Private = 6,
+    Function = 7,
+    Generic = 8,
+    PushConstant = 9,
+    AtomicCounter = 10,
+    Image = 11,
+    StorageBuffer = 12,
+    CallableData = 5328,
+    IncomingCallableData = 5329,
+    RayPayload = 5338,
+    HitAttribute = 5339,
+    IncomingRayPayload = 5342,
+    ShaderRecordBuffer = 5343,
+    PhysicalStorageBuffer = 5349,
+    /// A storage class without a variant of its own, such as `TaskPayloadWorkgroupEXT` or
+    /// `CodeSectionINTEL`, along with its raw value.
+    ///
+    /// Only values which have no variant above are reported this way, so `Other(12)` is never
+    /// returned and does not compare equal to `StorageBuffer`.
+    Other(u32),
+}
+
+/// Pointer information of a type.
+///
+/// Types of variables are pointers, as are buffer references in shaders using
+/// `PhysicalStorageBuffer` addressing.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointerType {
+    pub storage: StorageClass,
+    /// The ID of the type pointed to.
+    pub pointee_type_id: u32,
+    /// Number of pointer indirections, which is greater than 1 for pointers to pointers.
+    pub depth: u32,
+}
+
+/// A SPIR-V type.
+///
+/// Pointer types are described by the type they point to, with `pointer` set. Numeric types
+/// carry their bit `width`, and matrices of any component type have more than one column.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum Type {
-    // TODO: Add missing fields to relevant variants from SPIRType
     Unknown,
     Void,
     Boolean {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Char {
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Int {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     UInt {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Int64 {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     UInt64 {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     AtomicCounter {
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Half {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Float {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Double {
+        width: u32,
         vecsize: u32,
         columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Struct {
         member_types: Vec<u32>,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Image {
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
         image: ImageType,
+        pointer: Option<PointerType>,
     },
     SampledImage {
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
         image: ImageType,
+        pointer: Option<PointerType>,
     },
     Sampler {
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     SByte {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     UByte {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     Short {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     UShort {
+        width: u32,
         vecsize: u32,
+        columns: u32,
         array: Vec<u32>,
         array_size_literal: Vec<bool>,
+        pointer: Option<PointerType>,
     },
     ControlPointArray,
     AccelerationStructure,
@@ -519,12 +1114,291 @@ pub enum Type {
 #[derive(Debug, Clone)]
 pub struct Module<'a> {
     pub(crate) words: &'a [u32],
+    validation: bool,
 }
 
 impl<'a> Module<'a> {
     /// Creates a shader module from SPIR-V words.
     pub fn from_words(words: &[u32]) -> Module {
-        Module { words }
+        Module {
+            words,
+            validation: false,
+        }
+    }
+
+    /// Validates the module before it is parsed, so that malformed modules are rejected with
+    /// `ErrorCode::Validation` instead of reaching SPIRV-Cross.
+    pub fn with_validation(mut self) -> Self {
+        self.validation = true;
+        self
+    }
+
+    /// Checks the header, instruction layout and IDs of the module.
+    ///
+    /// This only checks the structure of the module, not whether it is semantically valid.
+    pub fn validate(&self) -> Result<(), ErrorCode> {
+        crate::validation::validate(self.words)
+    }
+
+    /// Runs validation if it was requested with `with_validation`.
+    pub(crate) fn validate_if_requested(&self) -> Result<(), ErrorCode> {
+        if self.validation {
+            self.validate()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The magic number which starts every SPIR-V module.
+pub const MAGIC_NUMBER: u32 = 0x0723_0203;
+
+/// The newest SPIR-V minor version accepted by `Header::from_words`.
+const MAX_MINOR_VERSION: u8 = 6;
+
+/// The five-word header at the start of a SPIR-V module.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Header {
+    /// The major SPIR-V version.
+    pub major_version: u8,
+    /// The minor SPIR-V version.
+    pub minor_version: u8,
+    /// The magic number of the tool which generated the module. `0` means the tool is unknown.
+    pub generator: u32,
+    /// An upper bound on every ID used in the module.
+    pub bound: u32,
+    /// The instruction schema, which is reserved and always `0`.
+    pub schema: u32,
+}
+
+impl Header {
+    /// The number of words in a SPIR-V header.
+    pub const WORD_COUNT: usize = 5;
+
+    /// Parses and validates the header at the start of a module in native byte order.
+    pub fn from_words(words: &[u32]) -> Result<Self, HeaderError> {
+        if words.len() < Self::WORD_COUNT {
+            return Err(HeaderError::Truncated { words: words.len() });
+        }
+        if words[0] != MAGIC_NUMBER {
+            return Err(HeaderError::InvalidMagic(words[0]));
+        }
+
+        let version = words[1];
+        let major_version = (version >> 16) as u8;
+        let minor_version = (version >> 8) as u8;
+        if version & 0xff00_00ff != 0 || major_version != 1 || minor_version > MAX_MINOR_VERSION {
+            return Err(HeaderError::UnsupportedVersion(version));
+        }
+
+        // Any generator is allowed, since tools register their magic numbers independently of
+        // the specification and `0` is reserved for unknown tools.
+        let generator = words[2];
+
+        let bound = words[3];
+        if bound == 0 {
+            return Err(HeaderError::InvalidBound);
+        }
+
+        let schema = words[4];
+        if schema != 0 {
+            return Err(HeaderError::InvalidSchema(schema));
+        }
+
+        Ok(Header {
+            major_version,
+            minor_version,
+            generator,
+            bound,
+            schema,
+        })
+    }
+
+    /// The registered ID of the tool which generated the module.
+    pub fn generator_tool(&self) -> u16 {
+        (self.generator >> 16) as u16
+    }
+
+    /// The version of the tool which generated the module.
+    pub fn generator_version(&self) -> u16 {
+        self.generator as u16
+    }
+}
+
+/// A reason why a SPIR-V module header was rejected.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum HeaderError {
+    /// The module is shorter than its header.
+    Truncated { words: usize },
+    /// The module's byte length is not a multiple of the word size.
+    UnalignedLength { bytes: usize },
+    /// The module does not start with `MAGIC_NUMBER` in either byte order.
+    InvalidMagic(u32),
+    /// The version word is malformed or names a version which isn't supported.
+    UnsupportedVersion(u32),
+    /// The ID bound is `0`, so the module can't declare any IDs.
+    InvalidBound,
+    /// The reserved schema word is not `0`.
+    InvalidSchema(u32),
+}
+
+impl std::fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            HeaderError::Truncated { words } => write!(
+                f,
+                "module has {} words, but the header needs {}",
+                words,
+                Header::WORD_COUNT
+            ),
+            HeaderError::UnalignedLength { bytes } => {
+                write!(f, "module length of {} bytes is not a multiple of 4", bytes)
+            }
+            HeaderError::InvalidMagic(magic) => write!(f, "invalid magic number {:#010x}", magic),
+            HeaderError::UnsupportedVersion(version) => {
+                write!(f, "unsupported version word {:#010x}", version)
+            }
+            HeaderError::InvalidBound => write!(f, "ID bound is 0"),
+            HeaderError::InvalidSchema(schema) => write!(f, "reserved schema is {}", schema),
+        }
+    }
+}
+
+impl std::error::Error for HeaderError {}
+
+/// An owned SPIR-V shader module with a validated header.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub struct ModuleBuf {
+    words: Vec<u32>,
+    header: Header,
+}
+
+impl ModuleBuf {
+    /// Creates a shader module from SPIR-V words, swapping their byte order if the magic number
+    /// shows they were written on a machine with a different endianness.
+    pub fn from_words(mut words: Vec<u32>) -> Result<Self, ErrorCode> {
+        if words.first() == Some(&MAGIC_NUMBER.swap_bytes()) {
+            for word in &mut words {
+                *word = word.swap_bytes();
+            }
+        }
+        let header = Header::from_words(&words).map_err(ErrorCode::InvalidHeader)?;
+        Ok(ModuleBuf { words, header })
+    }
+
+    /// Creates a shader module from the bytes of a SPIR-V binary in either byte order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ErrorCode> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(ErrorCode::InvalidHeader(HeaderError::UnalignedLength {
+                bytes: bytes.len(),
+            }));
+        }
+        if bytes.len() < Header::WORD_COUNT * 4 {
+            return Err(ErrorCode::InvalidHeader(HeaderError::Truncated {
+                words: bytes.len() / 4,
+            }));
+        }
+
+        let magic = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        let from_bytes: fn([u8; 4]) -> u32 = if u32::from_le_bytes(magic) == MAGIC_NUMBER {
+            u32::from_le_bytes
+        } else if u32::from_be_bytes(magic) == MAGIC_NUMBER {
+            u32::from_be_bytes
+        } else {
+            return Err(ErrorCode::InvalidHeader(HeaderError::InvalidMagic(
+                u32::from_le_bytes(magic),
+            )));
+        };
+
+        let words = bytes
+            .chunks_exact(4)
+            .map(|word| from_bytes([word[0], word[1], word[2], word[3]]))
+            .collect::<Vec<_>>();
+        let header = Header::from_words(&words).map_err(ErrorCode::InvalidHeader)?;
+        Ok(ModuleBuf { words, header })
+    }
+
+    /// Reads a SPIR-V binary in either byte order to the end.
+    pub fn from_reader<R: std::io::Read>(mut reader: R) -> Result<Self, ErrorCode> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(ErrorCode::from_io)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Reads a SPIR-V binary in either byte order from a file.
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ErrorCode> {
+        Self::from_bytes(&std::fs::read(path).map_err(ErrorCode::from_io)?)
+    }
+
+    /// Gets the validated header.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Gets the words of the module in native byte order.
+    pub fn words(&self) -> &[u32] {
+        &self.words
+    }
+
+    /// Borrows the module so that it can be parsed.
+    pub fn as_module(&self) -> Module<'_> {
+        Module::from_words(&self.words)
+    }
+
+    /// Consumes the module, returning its words in native byte order.
+    pub fn into_words(self) -> Vec<u32> {
+        self.words
+    }
+}
+
+impl<'a> From<&'a ModuleBuf> for Module<'a> {
+    fn from(module: &'a ModuleBuf) -> Self {
+        module.as_module()
+    }
+}
+
+/// A SPIR-V module which has been parsed once and can create any number of `Ast`s, for any
+/// target, without parsing the module again.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct ParsedModule {
+    pub(crate) ir: *mut crate::bindings::ScInternalParsedIr,
+}
+
+// SAFETY: The parsed IR is owned by `ParsedModule` and is never mutated after parsing. Creating
+// an `Ast` only reads it to make a copy, so it can be shared and sent between threads.
+#[cfg(not(target_arch = "wasm32"))]
+unsafe impl Send for ParsedModule {}
+#[cfg(not(target_arch = "wasm32"))]
+unsafe impl Sync for ParsedModule {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ParsedModule {
+    /// Parses a shader module.
+    pub fn parse(module: &Module) -> Result<Self, ErrorCode> {
+        module.validate_if_requested()?;
+        let mut ir = std::ptr::null_mut();
+        unsafe {
+            crate::check_result(crate::bindings::sc_internal_parsed_ir_new(
+                &mut ir,
+                module.words.as_ptr(),
+                module.words.len(),
+            ))
+            .map_err(ErrorCode::into_invalid_module)?;
+        }
+        Ok(ParsedModule { ir })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for ParsedModule {
+    fn drop(&mut self) {
+        unsafe {
+            crate::bindings::sc_internal_parsed_ir_delete(self.ir);
+        }
     }
 }
 
@@ -545,6 +1419,12 @@ pub trait Parse<TTarget>: Sized {
     fn parse(module: &Module) -> Result<Self, ErrorCode>;
 }
 
+/// Creates an `Ast` from a `ParsedModule` instead of parsing the module again.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait FromParsedModule<TTarget>: Sized {
+    fn from_parsed_module(module: &ParsedModule) -> Result<Self, ErrorCode>;
+}
+
 pub trait Compile<TTarget> {
     type CompilerOptions;
 
@@ -565,6 +1445,46 @@ where
         self.compiler.get_decoration(id, decoration)
     }
 
+    /// Checks whether a decoration is set, which tells a decoration with an argument of 0 apart
+    /// from an absent one.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn has_decoration(&self, id: u32, decoration: Decoration) -> Result<bool, ErrorCode> {
+        self.compiler.has_decoration(id, decoration)
+    }
+
+    /// Gets every decoration set on `id`, along with its argument.
+    ///
+    /// Decorations without a literal argument, including string decorations such as
+    /// `UserSemantic`, are listed with an argument of 1, see `get_decoration_string`.
+    /// Decorations which `Decoration` has no variant for are left out.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decorations(&self, id: u32) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        self.compiler.get_decorations(id)
+    }
+
+    /// Gets the argument of a `UserSemantic` decoration, the only string decoration SPIRV-Cross
+    /// keeps. If not defined, or for any other decoration, an empty string will be returned.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_decoration_string(
+        &self,
+        id: u32,
+        decoration: Decoration,
+    ) -> Result<String, ErrorCode> {
+        self.compiler.get_decoration_string(id, decoration)
+    }
+
+    /// Sets the argument of a `UserSemantic` decoration. SPIRV-Cross ignores any other decoration.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_decoration_string(
+        &mut self,
+        id: u32,
+        decoration: Decoration,
+        argument: &str,
+    ) -> Result<(), ErrorCode> {
+        self.compiler
+            .set_decoration_string(id, decoration, argument)
+    }
+
     /// Gets a name. If not defined, an empty string will be returned.
     pub fn get_name(&mut self, id: u32) -> Result<String, ErrorCode> {
         self.compiler.get_name(id)
@@ -600,6 +1520,17 @@ where
         self.compiler.get_entry_points()
     }
 
+    /// Gets the execution modes of an entry point.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_execution_modes(
+        &self,
+        entry_point_name: &str,
+        execution_model: ExecutionModel,
+    ) -> Result<ExecutionModes, ErrorCode> {
+        self.compiler
+            .get_execution_modes(entry_point_name, execution_model)
+    }
+
     /// Gets cleansed entry point names. `compile` must be called first.
     pub fn get_cleansed_entry_point_name(
         &self,
@@ -610,9 +1541,7 @@ where
             self.compiler
                 .get_cleansed_entry_point_name(entry_point_name, execution_model)
         } else {
-            Err(ErrorCode::CompilationError(String::from(
-                "`compile` must be called first",
-            )))
+            Err(ErrorCode::NotCompiled)
         }
     }
 
@@ -670,6 +1599,30 @@ where
             .set_member_decoration(id, index, decoration, argument)
     }
 
+    /// Unsets a decoration for a member located at `index` within an `OpTypeStruct`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn unset_member_decoration(
+        &mut self,
+        id: u32,
+        index: u32,
+        decoration: Decoration,
+    ) -> Result<(), ErrorCode> {
+        self.compiler.unset_member_decoration(id, index, decoration)
+    }
+
+    /// Gets every decoration set on the member located at `index` within an `OpTypeStruct`,
+    /// along with its argument.
+    ///
+    /// Decorations which `Decoration` has no variant for are left out.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_member_decorations(
+        &self,
+        id: u32,
+        index: u32,
+    ) -> Result<Vec<(Decoration, u32)>, ErrorCode> {
+        self.compiler.get_member_decorations(id, index)
+    }
+
     /// Gets the effective size of a buffer block.
     pub fn get_declared_struct_size(&self, id: u32) -> Result<u32, ErrorCode> {
         self.compiler.get_declared_struct_size(id)
@@ -680,6 +1633,47 @@ where
         self.compiler.get_declared_struct_member_size(id, index)
     }
 
+    /// Gets the layout of a struct type as declared by its decorations, including the layouts
+    /// of nested structs.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_struct_layout(&self, id: u32) -> Result<StructLayout, ErrorCode> {
+        self.compiler.get_struct_layout(id)
+    }
+
+    /// Compares the layout of a Rust type with the declared layout of a buffer block, returning
+    /// every difference found. An empty result means the type can be copied into the block.
+    ///
+    /// Trailing runtime arrays are skipped, as they can't be fields of a Rust struct.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn check_block_layout<T: ShaderBlock>(
+        &self,
+        resource: &Resource,
+    ) -> Result<Vec<LayoutMismatch>, ErrorCode> {
+        let layout = self.get_struct_layout(resource.base_type_id)?;
+        let mut mismatches = Vec::new();
+        let actual = std::mem::size_of::<T>();
+        if actual < layout.size as usize {
+            mismatches.push(LayoutMismatch::BlockSize {
+                expected: layout.size,
+                actual,
+            });
+        }
+        check_struct_layout(&layout, &T::fields(), "", &mut mismatches);
+        Ok(mismatches)
+    }
+
+    /// Computes the layout a struct type would have under the given rules, ignoring its
+    /// `Offset`, `ArrayStride` and `MatrixStride` decorations. The declared layout follows the
+    /// rules if it is equal to the result.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn compute_struct_layout(
+        &self,
+        id: u32,
+        layout: BlockLayout,
+    ) -> Result<StructLayout, ErrorCode> {
+        layout.struct_layout(self, id)
+    }
+
     /// Renames an interface variable.
     pub fn rename_interface_variable(
         &mut self,
@@ -722,4 +1716,462 @@ where
         self.compiler.has_been_compiled = true;
         Compile::<TTarget>::compile(self)
     }
+
+    /// Compiles an abstract syntax tree and collects the reflection data of the module.
+    pub fn compile_and_reflect(&mut self) -> Result<CompiledShader, ErrorCode> {
+        let source = self.compile()?;
+        Ok(CompiledShader {
+            source,
+            entry_points: self.get_entry_points()?,
+            shader_resources: self.get_shader_resources()?,
+            specialization_constants: self.get_specialization_constants()?,
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<TTarget> Ast<TTarget>
+where
+    Self: FromParsedModule<TTarget>,
+    TTarget: Target,
+{
+    /// Creates an `Ast` from an already parsed module.
+    pub fn from_parsed_module(module: &ParsedModule) -> Result<Self, ErrorCode> {
+        FromParsedModule::<TTarget>::from_parsed_module(module)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<TTarget> TypeLookup for Ast<TTarget>
+where
+    Self: Parse<TTarget> + Compile<TTarget>,
+    TTarget: Target,
+{
+    fn get_type(&self, id: u32) -> Result<Type, ErrorCode> {
+        self.compiler.get_type(id)
+    }
+
+    fn get_array_element_type(&self, id: u32) -> Result<u32, ErrorCode> {
+        self.compiler.get_array_element_type(id)
+    }
+
+    fn is_row_major(&self, struct_id: u32, index: u32) -> Result<bool, ErrorCode> {
+        Ok(self
+            .compiler
+            .get_member_decoration(struct_id, index, Decoration::RowMajor)?
+            != 0)
+    }
+
+    fn get_member_name(&self, struct_id: u32, index: u32) -> Result<String, ErrorCode> {
+        self.compiler.get_member_name(struct_id, index)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn check_struct_layout(
+    layout: &StructLayout,
+    fields: &[BlockField],
+    prefix: &str,
+    mismatches: &mut Vec<LayoutMismatch>,
+) {
+    fn normalize(name: &str) -> String {
+        name.chars()
+            .filter(|c| *c != '_')
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    let mut unmatched = fields
+        .iter()
+        .filter(|field| !field.name.starts_with('_'))
+        .collect::<Vec<_>>();
+
+    for member in &layout.members {
+        if member.array.last() == Some(&0) {
+            continue;
+        }
+
+        let name = normalize(&member.name);
+        let field = match unmatched
+            .iter()
+            .position(|field| normalize(field.name) == name)
+        {
+            Some(position) => unmatched.remove(position),
+            None => {
+                mismatches.push(LayoutMismatch::MissingField {
+                    member: format!("{}{}", prefix, member.name),
+                });
+                continue;
+            }
+        };
+        let path = format!("{}{}", prefix, field.name);
+
+        if field.offset != member.offset as usize {
+            mismatches.push(LayoutMismatch::Offset {
+                field: path.clone(),
+                expected: member.offset,
+                actual: field.offset,
+            });
+        }
+        if field.size != member.size as usize {
+            mismatches.push(LayoutMismatch::Size {
+                field: path.clone(),
+                expected: member.size,
+                actual: field.size,
+            });
+        }
+
+        // Member array dimensions correspond to the outermost Rust array levels; the levels
+        // within them hold the element, such as the columns of a matrix.
+        let dimensions = member.array.len();
+        let levels = field.array_strides.len();
+        if levels < dimensions {
+            mismatches.push(LayoutMismatch::ArrayDimensions {
+                field: path,
+                expected: dimensions,
+                actual: levels,
+            });
+            continue;
+        }
+        let element_levels = levels - dimensions;
+        for (dimension, (&expected, &actual)) in member
+            .array_strides
+            .iter()
+            .zip(&field.array_strides[element_levels..])
+            .enumerate()
+        {
+            if actual != expected as usize {
+                mismatches.push(LayoutMismatch::ArrayStride {
+                    field: path.clone(),
+                    dimension,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if let (Some(matrix), Some(&actual)) = (
+            member.matrix,
+            element_levels
+                .checked_sub(1)
+                .and_then(|level| field.array_strides.get(level)),
+        ) {
+            if actual != matrix.stride as usize {
+                mismatches.push(LayoutMismatch::MatrixStride {
+                    field: path.clone(),
+                    expected: matrix.stride,
+                    actual,
+                });
+            }
+        }
+
+        if let (Some(layout), Some(fields)) = (&member.struct_layout, field.fields) {
+            check_struct_layout(layout, &fields(), &format!("{}.", path), mismatches);
+        }
+    }
+
+    for field in unmatched {
+        mismatches.push(LayoutMismatch::UnexpectedField {
+            field: format!("{}{}", prefix, field.name),
+        });
+    }
+}
+
+/// Size and alignment of a struct member, or of an array element.
+struct Placement {
+    /// Size as reported by `get_declared_struct_member_size`, which excludes the trailing
+    /// padding of structs.
+    size: u32,
+    /// Size including trailing padding, which later members and array elements are placed after.
+    padded_size: u32,
+    alignment: u32,
+}
+
+impl BlockLayout {
+    /// Computes the layout of a struct type under these rules.
+    ///
+    /// As with `Ast::get_struct_layout`, struct sizes exclude trailing padding. Arrays sized by
+    /// specialization constants are rejected.
+    pub fn struct_layout<T: TypeLookup + ?Sized>(
+        self,
+        types: &T,
+        id: u32,
+    ) -> Result<StructLayout, ErrorCode> {
+        Ok(self.lay_out_struct(types, id)?.0)
+    }
+
+    fn lay_out_struct<T: TypeLookup + ?Sized>(
+        self,
+        types: &T,
+        id: u32,
+    ) -> Result<(StructLayout, Placement), ErrorCode> {
+        let member_types = match types.get_type(id)? {
+            Type::Struct {
+                member_types,
+                pointer: None,
+                ..
+            } => member_types,
+            _ => {
+                return Err(ErrorCode::CompilationError(format!(
+                    "type {} is not a struct",
+                    id
+                )))
+            }
+        };
+
+        let mut members = Vec::with_capacity(member_types.len());
+        let mut size = 0;
+        let mut end = 0;
+        let mut alignment = self.aggregate_alignment(1);
+        for (type_id, index) in member_types.into_iter().zip(0..) {
+            let (mut member, placement) = self.lay_out_member(types, id, index, type_id)?;
+            member.offset = align_to(end, placement.alignment);
+            size = member.offset + placement.size;
+            end = member.offset + placement.padded_size;
+            alignment = alignment.max(placement.alignment);
+            members.push(member);
+        }
+
+        let layout = StructLayout {
+            type_id: id,
+            size,
+            members,
+        };
+        let placement = Placement {
+            size,
+            padded_size: align_to(end, alignment),
+            alignment,
+        };
+        Ok((layout, placement))
+    }
+
+    /// Lays out a member, leaving its offset to the caller.
+    fn lay_out_member<T: TypeLookup + ?Sized>(
+        self,
+        types: &T,
+        struct_id: u32,
+        index: u32,
+        type_id: u32,
+    ) -> Result<(StructMemberLayout, Placement), ErrorCode> {
+        let unsupported = || {
+            ErrorCode::CompilationError(format!(
+                "member {} of struct {} cannot be stored in a buffer",
+                index, struct_id
+            ))
+        };
+
+        let (component, vecsize, columns, array, array_size_literal, pointer) =
+            match types.get_type(type_id)? {
+                Type::Boolean {
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                    ..
+                } => (
+                    Some(4),
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                ),
+                Type::Int {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::UInt {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::Int64 {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::UInt64 {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::Half {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::Float {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::Double {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::SByte {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::UByte {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::Short {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                }
+                | Type::UShort {
+                    width,
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                } => (
+                    Some(width / 8),
+                    vecsize,
+                    columns,
+                    array,
+                    array_size_literal,
+                    pointer,
+                ),
+                Type::Struct {
+                    array,
+                    array_size_literal,
+                    pointer,
+                    ..
+                } => (None, 1, 1, array, array_size_literal, pointer),
+                _ => return Err(unsupported()),
+            };
+
+        let mut matrix = None;
+        let mut struct_layout = None;
+        let mut placement = if pointer.is_some() {
+            // Physical storage buffer pointers are 64-bit addresses.
+            Placement {
+                size: 8,
+                padded_size: 8,
+                alignment: 8,
+            }
+        } else if let Some(component) = component {
+            if columns > 1 {
+                let row_major = types.is_row_major(struct_id, index)?;
+                let (major, minor) = if row_major {
+                    (vecsize, columns)
+                } else {
+                    (columns, vecsize)
+                };
+                let alignment = self.vector_alignment(component, minor);
+                let stride = self.array_stride(component * minor, alignment);
+                matrix = Some(MatrixLayout { stride, row_major });
+                Placement {
+                    size: stride * major,
+                    padded_size: stride * major,
+                    alignment: self.aggregate_alignment(alignment),
+                }
+            } else {
+                Placement {
+                    size: component * vecsize,
+                    padded_size: component * vecsize,
+                    alignment: self.vector_alignment(component, vecsize),
+                }
+            }
+        } else {
+            let (layout, placement) =
+                self.lay_out_struct(types, types.get_array_element_type(type_id)?)?;
+            struct_layout = Some(layout);
+            placement
+        };
+
+        let mut array_strides = Vec::with_capacity(array.len());
+        for (&length, &literal) in array.iter().zip(&array_size_literal) {
+            if !literal {
+                return Err(ErrorCode::CompilationError(format!(
+                    "member {} of struct {} is an array sized by a specialization constant",
+                    index, struct_id
+                )));
+            }
+            let stride = self.array_stride(placement.padded_size, placement.alignment);
+            array_strides.push(stride);
+            placement = Placement {
+                size: stride * length,
+                padded_size: stride * length,
+                alignment: self.aggregate_alignment(placement.alignment),
+            };
+        }
+
+        let member = StructMemberLayout {
+            index,
+            name: types.get_member_name(struct_id, index)?,
+            type_id,
+            offset: 0,
+            size: placement.size,
+            array,
+            array_size_literal,
+            array_strides,
+            matrix,
+            struct_layout,
+        };
+        Ok((member, placement))
+    }
+
+    fn vector_alignment(self, component: u32, vecsize: u32) -> u32 {
+        match (self, vecsize) {
+            (BlockLayout::Scalar, _) | (_, 1) => component,
+            (_, 2) => component * 2,
+            _ => component * 4,
+        }
+    }
+
+    /// Alignment of an array or struct whose elements or members have the given alignment.
+    fn aggregate_alignment(self, alignment: u32) -> u32 {
+        match self {
+            BlockLayout::Std140 => alignment.max(16),
+            BlockLayout::Std430 | BlockLayout::Scalar => alignment,
+        }
+    }
+
+    fn array_stride(self, size: u32, alignment: u32) -> u32 {
+        align_to(size, self.aggregate_alignment(alignment))
+    }
+}
+
+fn align_to(offset: u32, alignment: u32) -> u32 {
+    offset.div_ceil(alignment) * alignment
 }
@@ -0,0 +1,542 @@
+//! A structural validator for SPIR-V modules, which rejects malformed input before it reaches the
+//! SPIRV-Cross parser.
+//!
+//! Only the physical layout of the module is checked: instruction word counts, operand counts,
+//! string termination, ID bounds and that every referenced ID is defined somewhere in the module.
+//! Instructions with opcodes unknown to the validator only have their word count checked.
+//! Semantic rules, such as type agreement or control flow structure, are left to SPIRV-Cross.
+
+use crate::spirv::Header;
+use crate::ErrorCode;
+use std::collections::{HashMap, HashSet};
+
+/// A reason why a module was rejected by `spirv::Module::validate`.
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// An instruction declares a word count of zero.
+    ZeroWordCount,
+    /// An instruction extends past the end of the module.
+    InstructionOutOfBounds { word_count: u16 },
+    /// An instruction ends before all of its required operands.
+    MissingOperands { opcode: u16 },
+    /// An instruction has more operands than its opcode allows.
+    ExtraOperands { opcode: u16 },
+    /// A literal string is not terminated within its instruction.
+    UnterminatedString { opcode: u16 },
+    /// An ID is `0` or is not below the ID bound from the header.
+    IdOutOfBounds { id: u32, bound: u32 },
+    /// An ID is the result of more than one instruction.
+    DuplicateId(u32),
+    /// An ID is referenced, but no instruction defines it.
+    UndefinedId(u32),
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ValidationError::ZeroWordCount => write!(f, "instruction has a word count of 0"),
+            ValidationError::InstructionOutOfBounds { word_count } => write!(
+                f,
+                "instruction of {} words extends past the end of the module",
+                word_count
+            ),
+            ValidationError::MissingOperands { opcode } => {
+                write!(f, "instruction with opcode {} is missing operands", opcode)
+            }
+            ValidationError::ExtraOperands { opcode } => {
+                write!(f, "instruction with opcode {} has extra operands", opcode)
+            }
+            ValidationError::UnterminatedString { opcode } => write!(
+                f,
+                "instruction with opcode {} has an unterminated string",
+                opcode
+            ),
+            ValidationError::IdOutOfBounds { id, bound } => {
+                write!(f, "ID {} is outside the bound of {}", id, bound)
+            }
+            ValidationError::DuplicateId(id) => write!(f, "ID {} is defined more than once", id),
+            ValidationError::UndefinedId(id) => write!(f, "ID {} is never defined", id),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// The kind of an operand, following the SPIR-V grammar.
+#[derive(Copy, Clone, Debug)]
+enum Operand {
+    ResultType,
+    ResultId,
+    Id,
+    Literal,
+    LiteralString,
+    OptionalId,
+    OptionalLiteralString,
+    /// IDs until the end of the instruction.
+    Ids,
+    /// Literals until the end of the instruction.
+    Literals,
+    /// Strings until the end of the instruction.
+    LiteralStrings,
+    /// An optional image operands mask, followed by IDs.
+    ImageOperands,
+    /// Optional memory access masks, each followed by their literal and ID parameters.
+    MemoryAccess,
+    /// Pairs of an ID and a literal until the end of the instruction.
+    IdLiteralPairs,
+    /// Pairs of a selector literal and a label ID, where the literal width depends on the type of
+    /// the first operand.
+    SwitchTargets,
+    /// Operands which are not checked, because whether each one is a literal or an ID depends on
+    /// an extended instruction set or on the opcode given by an earlier operand.
+    Any,
+}
+
+use self::Operand::*;
+
+const NONE: &[Operand] = &[];
+const RESULT: &[Operand] = &[ResultId];
+const TYPED: &[Operand] = &[ResultType, ResultId];
+const TYPED_IDS: &[Operand] = &[ResultType, ResultId, Ids];
+const TYPED_LITERALS: &[Operand] = &[ResultType, ResultId, Literals];
+const TYPED_ID: &[Operand] = &[ResultType, ResultId, Id];
+const IDS: &[Operand] = &[Ids];
+const ID: &[Operand] = &[Id];
+const STRING: &[Operand] = &[LiteralString];
+const GROUP_OPERATION: &[Operand] = &[ResultType, ResultId, Id, Literal, Ids];
+const IMAGE_SAMPLE: &[Operand] = &[ResultType, ResultId, Id, Id, ImageOperands];
+const IMAGE_SAMPLE_DREF: &[Operand] = &[ResultType, ResultId, Id, Id, Id, ImageOperands];
+
+/// Gets the operand layout of an opcode, or `None` if the opcode is unknown, such as an opcode
+/// from a newer version of SPIR-V or an extension.
+#[rustfmt::skip]
+fn operand_layout(opcode: u16) -> Option<&'static [Operand]> {
+    Some(match opcode {
+        // OpNop, OpFunctionEnd, OpEmitVertex, OpEndPrimitive, OpKill, OpReturn, OpUnreachable,
+        // OpNoLine, OpTerminateInvocation, OpIgnoreIntersectionKHR, OpTerminateRayKHR,
+        // OpIgnoreIntersectionNV, OpTerminateRayNV, OpBeginInvocationInterlockEXT,
+        // OpEndInvocationInterlockEXT, OpDemoteToHelperInvocation
+        0 | 56 | 218 | 219 | 252 | 253 | 255 | 317 | 4416 | 4448 | 4449 | 5335 | 5336 | 5364
+        | 5365 | 5380 => NONE,
+        // OpUndef, OpConstantTrue, OpConstantFalse, OpConstantNull, OpSpecConstantTrue,
+        // OpSpecConstantFalse, OpFunctionParameter, OpCreateUserEvent, OpGetDefaultQueue,
+        // OpIsHelperInvocationEXT
+        1 | 41 | 42 | 46 | 48 | 49 | 55 | 299 | 303 | 5381 => TYPED,
+        // OpSourceContinued, OpSourceExtension, OpExtension, OpModuleProcessed
+        2 | 4 | 10 | 330 => STRING,
+        // OpSource
+        3 => &[Literal, Literal, OptionalId, OptionalLiteralString],
+        // OpName
+        5 => &[Id, LiteralString],
+        // OpMemberName
+        6 => &[Id, Literal, LiteralString],
+        // OpString, OpExtInstImport, OpTypeOpaque
+        7 | 11 | 31 => &[ResultId, LiteralString],
+        // OpLine
+        8 => &[Id, Literal, Literal],
+        // OpExtInst
+        12 => &[ResultType, ResultId, Id, Literal, Any],
+        // OpMemoryModel
+        14 => &[Literal, Literal],
+        // OpEntryPoint
+        15 => &[Literal, Id, LiteralString, Ids],
+        // OpExecutionMode
+        16 => &[Id, Literal, Literals],
+        // OpCapability
+        17 => &[Literal],
+        // OpTypeVoid, OpTypeBool, OpTypeSampler, OpTypeEvent, OpTypeDeviceEvent,
+        // OpTypeReserveId, OpTypeQueue, OpDecorationGroup, OpLabel, OpTypePipeStorage,
+        // OpTypeNamedBarrier, OpTypeRayQueryKHR, OpTypeAccelerationStructureKHR
+        19 | 20 | 26 | 34 | 35 | 36 | 37 | 73 | 248 | 322 | 327 | 4472 | 5341 => RESULT,
+        // OpTypeInt
+        21 => &[ResultId, Literal, Literal],
+        // OpTypeFloat
+        22 => &[ResultId, Literal, Literals],
+        // OpTypePipe
+        38 => &[ResultId, Literal],
+        // OpTypeVector, OpTypeMatrix
+        23 | 24 => &[ResultId, Id, Literal],
+        // OpTypeImage
+        25 => &[ResultId, Id, Literals],
+        // OpTypeSampledImage, OpTypeRuntimeArray
+        27 | 29 => &[ResultId, Id],
+        // OpTypeArray
+        28 => &[ResultId, Id, Id],
+        // OpTypeStruct, OpTypeFunction
+        30 | 33 => &[ResultId, Ids],
+        // OpTypePointer
+        32 => &[ResultId, Literal, Id],
+        // OpTypeForwardPointer
+        39 => &[Id, Literal],
+        // OpConstant, OpSpecConstant
+        43 | 50 => TYPED_LITERALS,
+        // OpConstantSampler, OpConstantPipeStorage
+        45 | 323 => &[ResultType, ResultId, Literal, Literal, Literal],
+        // OpSpecConstantOp
+        52 => &[ResultType, ResultId, Literal, Any],
+        // OpFunction
+        54 => &[ResultType, ResultId, Literal, Id],
+        // OpVariable
+        59 => &[ResultType, ResultId, Literal, OptionalId],
+        // OpLoad
+        61 => &[ResultType, ResultId, Id, MemoryAccess],
+        // OpStore, OpCopyMemory
+        62 | 63 => &[Id, Id, MemoryAccess],
+        // OpCopyMemorySized
+        64 => &[Id, Id, Id, MemoryAccess],
+        // OpArrayLength
+        68 => &[ResultType, ResultId, Id, Literal],
+        // OpDecorate
+        71 => &[Id, Literal, Literals],
+        // OpMemberDecorate
+        72 => &[Id, Literal, Literal, Literals],
+        // OpGroupMemberDecorate
+        75 => &[Id, IdLiteralPairs],
+        // OpVectorShuffle
+        79 => &[ResultType, ResultId, Id, Id, Literals],
+        // OpCompositeExtract
+        81 => &[ResultType, ResultId, Id, Literals],
+        // OpCompositeInsert
+        82 => &[ResultType, ResultId, Id, Id, Literals],
+        // OpImageSampleImplicitLod, OpImageSampleExplicitLod, OpImageSampleProjImplicitLod,
+        // OpImageSampleProjExplicitLod, OpImageFetch, OpImageRead and their sparse variants
+        87 | 88 | 91 | 92 | 95 | 98 | 305 | 306 | 309 | 310 | 313 | 320 => IMAGE_SAMPLE,
+        // OpImageSampleDref*, OpImageSampleProjDref*, OpImageGather, OpImageDrefGather and their
+        // sparse variants
+        89 | 90 | 93 | 94 | 96 | 97 | 307 | 308 | 311 | 312 | 314 | 315 => IMAGE_SAMPLE_DREF,
+        // OpImageWrite
+        99 => &[Id, Id, Id, ImageOperands],
+        // OpImage, OpImageQueryFormat, OpImageQueryOrder, OpImageQuerySize, OpImageQueryLevels,
+        // OpImageQuerySamples, OpImageSparseTexelsResident, OpSizeOf,
+        // OpCreatePipeFromPipeStorage, OpNamedBarrierInitialize, OpGroupNonUniformElect,
+        // OpCopyLogical, OpSubgroupBallotKHR, OpSubgroupFirstInvocationKHR, OpSubgroupAllKHR,
+        // OpSubgroupAnyKHR, OpSubgroupAllEqualKHR, OpConvertUToAccelerationStructureKHR,
+        // OpRayQueryProceedKHR, OpReadClockKHR, OpGroupNonUniformPartitionNV
+        100 | 101 | 102 | 104 | 106 | 107 | 316 | 321 | 324 | 328 | 333 | 400 | 4421 | 4422
+        | 4428 | 4429 | 4430 | 4447 | 4477 | 5056 | 5296 => TYPED_ID,
+        // OpGenericCastToPtrExplicit
+        123 => &[ResultType, ResultId, Id, Literal],
+        // OpEmitStreamVertex, OpEndStreamPrimitive, OpBranch, OpReturnValue,
+        // OpRayQueryTerminateKHR, OpRayQueryConfirmIntersectionKHR
+        220 | 221 | 249 | 254 | 4474 | 4476 => ID,
+        // OpGroupDecorate, OpControlBarrier, OpMemoryBarrier, OpAtomicStore, OpGroupWaitEvents,
+        // OpCommitReadPipe, OpCommitWritePipe, OpGroupCommitReadPipe, OpGroupCommitWritePipe,
+        // OpRetainEvent, OpReleaseEvent, OpSetUserEventStatus, OpCaptureEventProfilingInfo,
+        // OpAtomicFlagClear, OpMemoryNamedBarrier, OpTraceRayKHR, OpExecuteCallableKHR,
+        // OpRayQueryInitializeKHR, OpRayQueryGenerateIntersectionKHR, OpEmitMeshTasksEXT,
+        // OpSetMeshOutputsEXT, OpWritePackedPrimitiveIndices4x8NV, OpTraceNV, OpTraceMotionNV,
+        // OpTraceRayMotionNV, OpExecuteCallableNV
+        74 | 224 | 225 | 228 | 260 | 280 | 281 | 287 | 288 | 297 | 298 | 301 | 302 | 319 | 329
+        | 4445 | 4446 | 4473 | 4475 | 5294 | 5295 | 5299 | 5337 | 5338 | 5339 | 5344 => IDS,
+        // OpLoopMerge
+        246 => &[Id, Id, Literals],
+        // OpSelectionMerge
+        247 => &[Id, Literal],
+        // OpBranchConditional
+        250 => &[Id, Id, Id, Literals],
+        // OpSwitch
+        251 => &[Id, Id, SwitchTargets],
+        // OpLifetimeStart, OpLifetimeStop
+        256 | 257 => &[Id, Literal],
+        // OpGroupIAdd through OpGroupSMax, OpGroupNonUniformBallotBitCount,
+        // OpGroupNonUniformIAdd through OpGroupNonUniformLogicalXor, and the AMD group operations
+        264..=271 | 342 | 349..=364 | 5000..=5007 => GROUP_OPERATION,
+        // OpExecutionModeId
+        331 => &[Id, Literal, Ids],
+        // OpDecorateId
+        332 => &[Id, Literal, Ids],
+        // OpSDot, OpUDot, OpSUDot, OpSDotAccSat, OpUDotAccSat, OpSUDotAccSat
+        4450..=4452 => &[ResultType, ResultId, Id, Id, Literals],
+        4453..=4455 => &[ResultType, ResultId, Id, Id, Id, Literals],
+        // OpImageSampleFootprintNV
+        5283 => &[ResultType, ResultId, Id, Id, Id, Id, ImageOperands],
+        // OpDecorateString
+        5632 => &[Id, Literal, LiteralStrings],
+        // OpMemberDecorateString
+        5633 => &[Id, Literal, Literal, LiteralStrings],
+        // Instructions whose operands after the result are all IDs: access chains, composite,
+        // conversion, arithmetic, relational, bit, derivative, atomic, group, pipe, device-side
+        // enqueue, non-uniform and pointer instructions, including OpGroupNonUniformQuadBroadcast,
+        // OpGroupNonUniformQuadSwap and OpPtrEqual through OpPtrDiff, OpPhi, OpFunctionCall and
+        // OpReportIntersectionKHR.
+        44 | 51 | 57 | 60 | 65 | 66 | 67 | 69 | 70 | 77 | 78 | 80 | 83 | 84 | 86 | 103 | 105
+        | 109..=122 | 124 | 126..=152 | 154..=191 | 194..=205 | 207..=215 | 227 | 229..=242
+        | 245 | 259 | 261 | 262 | 263 | 274..=279 | 282..=286 | 291..=296 | 300 | 304 | 318
+        | 325 | 326 | 334..=341 | 343..=348 | 365 | 366 | 401..=403 | 4431 | 4432 | 4479
+        | 5011 | 5012 | 5334 => TYPED_IDS,
+        _ => return None,
+    })
+}
+
+/// Memory access mask bits which are followed by parameters.
+const MEMORY_ACCESS_ALIGNED: u32 = 0x2;
+const MEMORY_ACCESS_MAKE_POINTER_AVAILABLE: u32 = 0x8;
+const MEMORY_ACCESS_MAKE_POINTER_VISIBLE: u32 = 0x10;
+
+const OP_TYPE_INT: u16 = 21;
+
+/// Checks the layout and IDs of a module in native byte order.
+pub(crate) fn validate(words: &[u32]) -> Result<(), ErrorCode> {
+    let header = Header::from_words(words).map_err(ErrorCode::InvalidHeader)?;
+    let mut validator = Validator {
+        bound: header.bound,
+        defined: HashSet::new(),
+        result_types: HashMap::new(),
+        int_widths: HashMap::new(),
+        references: Vec::new(),
+    };
+
+    let mut unknown_opcodes = false;
+    let mut offset = Header::WORD_COUNT;
+    while offset < words.len() {
+        let word_count = (words[offset] >> 16) as u16;
+        let opcode = words[offset] as u16;
+        let error = |error| ErrorCode::Validation { offset, error };
+
+        if word_count == 0 {
+            return Err(error(ValidationError::ZeroWordCount));
+        }
+        let end = offset + word_count as usize;
+        if end > words.len() {
+            return Err(error(ValidationError::InstructionOutOfBounds {
+                word_count,
+            }));
+        }
+        match operand_layout(opcode) {
+            Some(layout) => {
+                validator.instruction(offset, opcode, layout, &words[offset + 1..end])?
+            }
+            None => unknown_opcodes = true,
+        }
+        offset = end;
+    }
+
+    // IDs may be referenced before they are defined, for example by debug instructions,
+    // decorations, forward pointers or `OpPhi`, so definitions are only checked at the end. The
+    // IDs defined by instructions with unknown opcodes can't be found, so the check is skipped.
+    if unknown_opcodes {
+        return Ok(());
+    }
+    for (offset, id) in validator.references {
+        if !validator.defined.contains(&id) {
+            return Err(ErrorCode::Validation {
+                offset,
+                error: ValidationError::UndefinedId(id),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+struct Validator {
+    bound: u32,
+    defined: HashSet<u32>,
+    /// The result type of each ID which has one, used to find the width of `OpSwitch` literals.
+    result_types: HashMap<u32, u32>,
+    /// The width of each integer type.
+    int_widths: HashMap<u32, u32>,
+    /// Every referenced ID along with the word offset of the reference.
+    references: Vec<(usize, u32)>,
+}
+
+/// Reads the operands of an instruction.
+struct Operands<'a> {
+    /// Word offset of the instruction.
+    offset: usize,
+    opcode: u16,
+    words: &'a [u32],
+    position: usize,
+}
+
+impl<'a> Operands<'a> {
+    fn error(&self, error: ValidationError) -> ErrorCode {
+        ErrorCode::Validation {
+            offset: self.offset,
+            error,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position == self.words.len()
+    }
+
+    /// Word offset of the next operand.
+    fn next_offset(&self) -> usize {
+        self.offset + 1 + self.position
+    }
+
+    fn word(&mut self) -> Result<u32, ErrorCode> {
+        let word = *self.words.get(self.position).ok_or_else(|| {
+            self.error(ValidationError::MissingOperands {
+                opcode: self.opcode,
+            })
+        })?;
+        self.position += 1;
+        Ok(word)
+    }
+
+    fn string(&mut self) -> Result<(), ErrorCode> {
+        if self.is_empty() {
+            return Err(self.error(ValidationError::MissingOperands {
+                opcode: self.opcode,
+            }));
+        }
+        while let Some(&word) = self.words.get(self.position) {
+            self.position += 1;
+            if word.to_le_bytes().contains(&0) {
+                return Ok(());
+            }
+        }
+        Err(self.error(ValidationError::UnterminatedString {
+            opcode: self.opcode,
+        }))
+    }
+}
+
+impl Validator {
+    fn instruction(
+        &mut self,
+        offset: usize,
+        opcode: u16,
+        layout: &[Operand],
+        words: &[u32],
+    ) -> Result<(), ErrorCode> {
+        let mut operands = Operands {
+            offset,
+            opcode,
+            words,
+            position: 0,
+        };
+        let mut result_type = None;
+        let mut result = None;
+
+        for operand in layout {
+            match operand {
+                ResultType => result_type = Some(self.reference(&mut operands)?),
+                ResultId => {
+                    let offset = operands.next_offset();
+                    let id = operands.word()?;
+                    self.check_bound(offset, id)?;
+                    if !self.defined.insert(id) {
+                        return Err(ErrorCode::Validation {
+                            offset,
+                            error: ValidationError::DuplicateId(id),
+                        });
+                    }
+                    result = Some(id);
+                }
+                Id => {
+                    self.reference(&mut operands)?;
+                }
+                Literal => {
+                    operands.word()?;
+                }
+                LiteralString => operands.string()?,
+                OptionalId => {
+                    if !operands.is_empty() {
+                        self.reference(&mut operands)?;
+                    }
+                }
+                OptionalLiteralString => {
+                    if !operands.is_empty() {
+                        operands.string()?;
+                    }
+                }
+                Ids => {
+                    while !operands.is_empty() {
+                        self.reference(&mut operands)?;
+                    }
+                }
+                Literals | Any => operands.position = operands.words.len(),
+                LiteralStrings => {
+                    while !operands.is_empty() {
+                        operands.string()?;
+                    }
+                }
+                ImageOperands => {
+                    if !operands.is_empty() {
+                        operands.word()?;
+                        while !operands.is_empty() {
+                            self.reference(&mut operands)?;
+                        }
+                    }
+                }
+                MemoryAccess => {
+                    while !operands.is_empty() {
+                        let mask = operands.word()?;
+                        if mask & MEMORY_ACCESS_ALIGNED != 0 {
+                            operands.word()?;
+                        }
+                        if mask & MEMORY_ACCESS_MAKE_POINTER_AVAILABLE != 0 {
+                            self.reference(&mut operands)?;
+                        }
+                        if mask & MEMORY_ACCESS_MAKE_POINTER_VISIBLE != 0 {
+                            self.reference(&mut operands)?;
+                        }
+                    }
+                }
+                IdLiteralPairs => {
+                    while !operands.is_empty() {
+                        self.reference(&mut operands)?;
+                        operands.word()?;
+                    }
+                }
+                SwitchTargets => {
+                    let selector_width = self
+                        .result_types
+                        .get(&words[0])
+                        .and_then(|ty| self.int_widths.get(ty))
+                        .copied()
+                        .unwrap_or(32);
+                    let literal_words = if selector_width > 32 { 2 } else { 1 };
+                    while !operands.is_empty() {
+                        for _ in 0..literal_words {
+                            operands.word()?;
+                        }
+                        self.reference(&mut operands)?;
+                    }
+                }
+            }
+        }
+
+        if !operands.is_empty() {
+            return Err(operands.error(ValidationError::ExtraOperands { opcode }));
+        }
+
+        match (result_type, result) {
+            (Some(ty), Some(id)) => {
+                self.result_types.insert(id, ty);
+            }
+            (None, Some(id)) if opcode == OP_TYPE_INT => {
+                self.int_widths.insert(id, words[1]);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn reference(&mut self, operands: &mut Operands) -> Result<u32, ErrorCode> {
+        let offset = operands.next_offset();
+        let id = operands.word()?;
+        self.check_bound(offset, id)?;
+        self.references.push((offset, id));
+        Ok(id)
+    }
+
+    fn check_bound(&self, offset: usize, id: u32) -> Result<(), ErrorCode> {
+        if id == 0 || id >= self.bound {
+            return Err(ErrorCode::Validation {
+                offset,
+                error: ValidationError::IdOutOfBounds {
+                    id,
+                    bound: self.bound,
+                },
+            });
+        }
+        Ok(())
+    }
+}
@@ -2,12 +2,20 @@
 #pragma warning(disable : 4996 4101)
 
 #include "vendor/SPIRV-Cross/spirv_cross_util.hpp"
+#include "vendor/SPIRV-Cross/spirv_parser.hpp"
 #include "vendor/SPIRV-Cross/spirv_hlsl.hpp"
 #include "vendor/SPIRV-Cross/spirv_msl.hpp"
 #include "vendor/SPIRV-Cross/spirv_glsl.hpp"
+#include "vendor/SPIRV-Cross/spirv_reflect.hpp"
+#include "vendor/SPIRV-Cross/spirv_cpp.hpp"
 #include "wrapper.hpp"
 
-static const char *latest_exception_message;
+#include <memory>
+
+// Message of the latest exception raised on the calling thread. Reading it through
+// `sc_internal_get_latest_exception_message` transfers ownership to the caller, so concurrent
+// compilers on different threads never observe or free each other's messages.
+static thread_local const char *latest_exception_message = nullptr;
 
 #ifdef SPIRV_CROSS_WRAPPER_NO_EXCEPTIONS
 #define INTERNAL_RESULT(block_to_attempt)                 \
@@ -31,26 +39,150 @@ static const char *latest_exception_message;
         }                                                 \
         catch (const spirv_cross::CompilerError &ex)      \
         {                                                 \
+            free((void *)latest_exception_message);       \
             latest_exception_message = strdup(ex.what()); \
             return ScInternalResult::CompilationError;    \
         }                                                 \
         catch (const std::exception &ex)                  \
         {                                                 \
+            free((void *)latest_exception_message);       \
+            latest_exception_message = strdup(ex.what()); \
             return ScInternalResult::Unhandled;           \
         }                                                 \
         catch (...)                                       \
         {                                                 \
+            free((void *)latest_exception_message);       \
+            latest_exception_message = nullptr;           \
             return ScInternalResult::Unhandled;           \
         }                                                 \
         return ScInternalResult::Unhandled;               \
     } while (0);
 #endif
 
+// SPIRV-Cross keeps some compiler state in protected members without public accessors. These
+// helpers reach it through pointers to members, so that cloning a compiler also copies it.
+struct ScCompilerAccess : spirv_cross::Compiler
+{
+    static const spirv_cross::ParsedIR &get_ir(const spirv_cross::Compiler &compiler)
+    {
+        return compiler.*(&ScCompilerAccess::ir);
+    }
+
+    static void copy_state(spirv_cross::Compiler &target, const spirv_cross::Compiler &source)
+    {
+        target.*(&ScCompilerAccess::combined_image_samplers) = source.*(&ScCompilerAccess::combined_image_samplers);
+    }
+};
+
+struct ScCompilerGlslAccess : spirv_cross::CompilerGLSL
+{
+    static void copy_state(spirv_cross::CompilerGLSL &target, spirv_cross::CompilerGLSL &source)
+    {
+        ScCompilerAccess::copy_state(target, source);
+        target.set_common_options(source.get_common_options());
+        target.*(&ScCompilerGlslAccess::header_lines) = source.*(&ScCompilerGlslAccess::header_lines);
+        target.*(&ScCompilerGlslAccess::flattened_buffer_blocks) = source.*(&ScCompilerGlslAccess::flattened_buffer_blocks);
+    }
+
+    static spirv_cross::CompilerGLSL *clone(spirv_cross::CompilerGLSL &source)
+    {
+        std::unique_ptr<spirv_cross::CompilerGLSL> clone(new spirv_cross::CompilerGLSL(ScCompilerAccess::get_ir(source)));
+        copy_state(*clone, source);
+        return clone.release();
+    }
+};
+
+#ifdef SPIRV_CROSS_WRAPPER_HLSL
+struct ScCompilerHlslAccess : spirv_cross::CompilerHLSL
+{
+    static spirv_cross::CompilerHLSL *clone(spirv_cross::CompilerHLSL &source)
+    {
+        std::unique_ptr<spirv_cross::CompilerHLSL> clone(new spirv_cross::CompilerHLSL(ScCompilerAccess::get_ir(source)));
+        ScCompilerGlslAccess::copy_state(*clone, source);
+        clone->set_hlsl_options(source.get_hlsl_options());
+        clone->set_root_constant_layouts(source.*(&ScCompilerHlslAccess::root_constants_layout));
+        return clone.release();
+    }
+};
+#endif
+
+#ifdef SPIRV_CROSS_WRAPPER_MSL
+struct ScCompilerMslAccess
+{
+    static spirv_cross::CompilerMSL *clone(spirv_cross::CompilerMSL &source)
+    {
+        std::unique_ptr<spirv_cross::CompilerMSL> clone(new spirv_cross::CompilerMSL(ScCompilerAccess::get_ir(source)));
+        ScCompilerGlslAccess::copy_state(*clone, source);
+        clone->set_msl_options(source.get_msl_options());
+        return clone.release();
+    }
+};
+#endif
+
+#ifdef SPIRV_CROSS_WRAPPER_REFLECT
+struct ScCompilerReflectionAccess
+{
+    static spirv_cross::CompilerReflection *clone(spirv_cross::CompilerReflection &source)
+    {
+        std::unique_ptr<spirv_cross::CompilerReflection> clone(new spirv_cross::CompilerReflection(ScCompilerAccess::get_ir(source)));
+        ScCompilerGlslAccess::copy_state(*clone, source);
+        return clone.release();
+    }
+};
+#endif
+
+#ifdef SPIRV_CROSS_WRAPPER_CPP
+// The interface name of `CompilerCPP` is private, so the Rust side sets it again on clones.
+struct ScCompilerCppAccess
+{
+    static spirv_cross::CompilerCPP *clone(spirv_cross::CompilerCPP &source)
+    {
+        std::unique_ptr<spirv_cross::CompilerCPP> clone(new spirv_cross::CompilerCPP(ScCompilerAccess::get_ir(source)));
+        ScCompilerGlslAccess::copy_state(*clone, source);
+        return clone.release();
+    }
+};
+#endif
+
+// Copies the bits set in `bitset`, such as decorations or execution modes, into a newly allocated
+// array, which the caller frees with `sc_internal_free_pointer`.
+static void copy_bitset(const spirv_cross::Bitset &bitset, uint32_t **values, size_t *size)
+{
+    std::vector<uint32_t> sc_values;
+    bitset.for_each_bit([&](uint32_t value) { sc_values.push_back(value); });
+
+    *size = sc_values.size();
+    *values = (uint32_t *)malloc(sc_values.size() * sizeof(uint32_t));
+    for (size_t i = 0; i < sc_values.size(); i++)
+    {
+        (*values)[i] = sc_values[i];
+    }
+}
+
 extern "C"
 {
     ScInternalResult sc_internal_get_latest_exception_message(const char **message)
     {
-        INTERNAL_RESULT(*message = latest_exception_message;)
+        INTERNAL_RESULT(
+            do {
+                *message = latest_exception_message;
+                latest_exception_message = nullptr;
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_parsed_ir_new(ScInternalParsedIr **ir, const uint32_t *words, const size_t size)
+    {
+        INTERNAL_RESULT(
+            do {
+                spirv_cross::Parser parser(words, size);
+                parser.parse();
+                *ir = new spirv_cross::ParsedIR(std::move(parser.get_parsed_ir()));
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_parsed_ir_delete(ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(delete (spirv_cross::ParsedIR *)ir;)
     }
 
 #ifdef SPIRV_CROSS_WRAPPER_HLSL
@@ -59,6 +191,16 @@ extern "C"
         INTERNAL_RESULT(*compiler = new spirv_cross::CompilerHLSL(ir, size);)
     }
 
+    ScInternalResult sc_internal_compiler_hlsl_new_from_parsed_ir(ScInternalCompilerHlsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerHLSL(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_hlsl_clone(ScInternalCompilerHlsl **clone, const ScInternalCompilerHlsl *compiler)
+    {
+        INTERNAL_RESULT(*clone = ScCompilerHlslAccess::clone(*(spirv_cross::CompilerHLSL *)compiler);)
+    }
+
     ScInternalResult sc_internal_compiler_hlsl_set_options(const ScInternalCompilerHlsl *compiler, const ScHlslCompilerOptions *options)
     {
         INTERNAL_RESULT(
@@ -109,6 +251,16 @@ extern "C"
         INTERNAL_RESULT(*compiler = new spirv_cross::CompilerMSL(ir, size);)
     }
 
+    ScInternalResult sc_internal_compiler_msl_new_from_parsed_ir(ScInternalCompilerMsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerMSL(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_msl_clone(ScInternalCompilerMsl **clone, const ScInternalCompilerMsl *compiler)
+    {
+        INTERNAL_RESULT(*clone = ScCompilerMslAccess::clone(*(spirv_cross::CompilerMSL *)compiler);)
+    }
+
     ScInternalResult sc_internal_compiler_msl_compile(const ScInternalCompilerBase *compiler, const char **shader,
                                                       const spirv_cross::MSLShaderInput *p_vat_overrides, const size_t vat_override_count,
                                                       const spirv_cross::MSLResourceBinding *p_res_overrides, const size_t res_override_count,
@@ -184,6 +336,16 @@ extern "C"
         INTERNAL_RESULT(*compiler = new spirv_cross::CompilerGLSL(ir, size);)
     }
 
+    ScInternalResult sc_internal_compiler_glsl_new_from_parsed_ir(ScInternalCompilerGlsl **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerGLSL(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_glsl_clone(ScInternalCompilerGlsl **clone, const ScInternalCompilerGlsl *compiler)
+    {
+        INTERNAL_RESULT(*clone = ScCompilerGlslAccess::clone(*(spirv_cross::CompilerGLSL *)compiler);)
+    }
+
     ScInternalResult sc_internal_compiler_glsl_set_options(const ScInternalCompilerGlsl *compiler, const ScGlslCompilerOptions *options)
     {
         INTERNAL_RESULT(
@@ -246,6 +408,45 @@ extern "C"
     }
 #endif
 
+#ifdef SPIRV_CROSS_WRAPPER_REFLECT
+    ScInternalResult sc_internal_compiler_reflection_new(ScInternalCompilerReflection **compiler, const uint32_t *ir, const size_t size)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerReflection(ir, size);)
+    }
+
+    ScInternalResult sc_internal_compiler_reflection_new_from_parsed_ir(ScInternalCompilerReflection **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerReflection(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_reflection_clone(ScInternalCompilerReflection **clone, const ScInternalCompilerReflection *compiler)
+    {
+        INTERNAL_RESULT(*clone = ScCompilerReflectionAccess::clone(*(spirv_cross::CompilerReflection *)compiler);)
+    }
+#endif
+
+#ifdef SPIRV_CROSS_WRAPPER_CPP
+    ScInternalResult sc_internal_compiler_cpp_new(ScInternalCompilerCpp **compiler, const uint32_t *ir, const size_t size)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerCPP(ir, size);)
+    }
+
+    ScInternalResult sc_internal_compiler_cpp_new_from_parsed_ir(ScInternalCompilerCpp **compiler, const ScInternalParsedIr *ir)
+    {
+        INTERNAL_RESULT(*compiler = new spirv_cross::CompilerCPP(*(const spirv_cross::ParsedIR *)ir);)
+    }
+
+    ScInternalResult sc_internal_compiler_cpp_clone(ScInternalCompilerCpp **clone, const ScInternalCompilerCpp *compiler)
+    {
+        INTERNAL_RESULT(*clone = ScCompilerCppAccess::clone(*(spirv_cross::CompilerCPP *)compiler);)
+    }
+
+    ScInternalResult sc_internal_compiler_cpp_set_interface_name(const ScInternalCompilerCpp *compiler, const char *name)
+    {
+        INTERNAL_RESULT(((spirv_cross::CompilerCPP *)compiler)->set_interface_name(name);)
+    }
+#endif
+
     ScInternalResult sc_internal_compiler_get_decoration(const ScInternalCompilerBase *compiler, uint32_t *result, const uint32_t id, const spv::Decoration decoration)
     {
         INTERNAL_RESULT(*result = ((spirv_cross::Compiler *)compiler)->get_decoration(id, decoration);)
@@ -256,11 +457,35 @@ extern "C"
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->unset_decoration(id, decoration);)
     }
 
+    ScInternalResult sc_internal_compiler_has_decoration(const ScInternalCompilerBase *compiler, bool *result, const uint32_t id, const spv::Decoration decoration)
+    {
+        INTERNAL_RESULT(*result = ((spirv_cross::Compiler *)compiler)->has_decoration(id, decoration);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_decoration_bitset(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t **decorations, size_t *size)
+    {
+        INTERNAL_RESULT(copy_bitset(((spirv_cross::Compiler *)compiler)->get_decoration_bitset(id), decorations, size);)
+    }
+
     ScInternalResult sc_internal_compiler_set_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const uint32_t argument)
     {
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_decoration(id, decoration, argument);)
     }
 
+    ScInternalResult sc_internal_compiler_get_decoration_string(const ScInternalCompilerBase *compiler, const char **result, const uint32_t id, const spv::Decoration decoration)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &_result = ((spirv_cross::Compiler *)compiler)->get_decoration_string(id, decoration);
+                *result = strdup(_result.c_str());
+            } while (0);)
+    }
+
+    ScInternalResult sc_internal_compiler_set_decoration_string(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const char *argument)
+    {
+        INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_decoration_string(id, decoration, std::string(argument));)
+    }
+
     ScInternalResult sc_internal_compiler_get_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name)
     {
         INTERNAL_RESULT(
@@ -304,6 +529,21 @@ extern "C"
             } while (0);)
     }
 
+    ScInternalResult sc_internal_compiler_get_execution_modes(const ScInternalCompilerBase *compiler, const char *entry_point_name, const spv::ExecutionModel execution_model, ScExecutionModes *execution_modes)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &comp = *((spirv_cross::Compiler *)compiler);
+                auto const &sc_spir_entry_point = comp.get_entry_point(entry_point_name, execution_model);
+                copy_bitset(sc_spir_entry_point.flags, &execution_modes->modes, &execution_modes->modes_size);
+                execution_modes->invocations = sc_spir_entry_point.invocations;
+                execution_modes->output_vertices = sc_spir_entry_point.output_vertices;
+                execution_modes->local_size_id_x = sc_spir_entry_point.workgroup_size.id_x;
+                execution_modes->local_size_id_y = sc_spir_entry_point.workgroup_size.id_y;
+                execution_modes->local_size_id_z = sc_spir_entry_point.workgroup_size.id_z;
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_get_active_buffer_ranges(const ScInternalCompilerBase *compiler, uint32_t id, ScBufferRange **active_buffer_ranges, size_t *size)
     {
         INTERNAL_RESULT(
@@ -411,7 +651,8 @@ extern "C"
     {
         INTERNAL_RESULT(
             do {
-                auto const &type = ((spirv_cross::Compiler *)compiler)->get_type(id);
+                auto const &comp = *((spirv_cross::Compiler *)compiler);
+                auto const &type = comp.get_type(id);
                 auto const member_types_size = type.member_types.size();
                 auto const array_size = type.array.size();
 
@@ -423,6 +664,25 @@ extern "C"
                 ty->member_types_size = member_types_size;
                 ty->array_size = array_size;
                 ty->storage = type.storage;
+                ty->pointer = type.pointer;
+                ty->pointer_depth = type.pointer_depth;
+                ty->pointee_type = 0;
+
+                if (type.pointer)
+                {
+                    // Arrays of pointers are flagged as pointers too, so find the pointer type itself.
+                    auto const *pointer_type = &type;
+                    while (!pointer_type->array.empty())
+                    {
+                        auto const &parent = comp.get_type(pointer_type->parent_type);
+                        if (!parent.pointer || parent.pointer_depth != pointer_type->pointer_depth)
+                        {
+                            break;
+                        }
+                        pointer_type = &parent;
+                    }
+                    ty->pointee_type = pointer_type->parent_type;
+                }
 
                 if (member_types_size > 0)
                 {
@@ -479,6 +739,16 @@ extern "C"
         INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->set_member_decoration(id, index, decoration, argument);)
     }
 
+    ScInternalResult sc_internal_compiler_unset_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration)
+    {
+        INTERNAL_RESULT(((spirv_cross::Compiler *)compiler)->unset_member_decoration(id, index, decoration);)
+    }
+
+    ScInternalResult sc_internal_compiler_get_member_decoration_bitset(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, uint32_t **decorations, size_t *size)
+    {
+        INTERNAL_RESULT(copy_bitset(((spirv_cross::Compiler *)compiler)->get_member_decoration_bitset(id, index), decorations, size);)
+    }
+
     ScInternalResult sc_internal_compiler_get_declared_struct_size(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *result)
     {
         INTERNAL_RESULT(do {
@@ -495,6 +765,46 @@ extern "C"
         } while (0);)
     }
 
+    ScInternalResult sc_internal_compiler_get_array_layout(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *element_type, ScArrayDimension **dimensions, size_t *size)
+    {
+        INTERNAL_RESULT(
+            do {
+                auto const &comp = *((spirv_cross::Compiler *)compiler);
+
+                // Each array dimension is its own type wrapping the next inner one. Dimensions are
+                // returned innermost first, matching `SPIRType::array`.
+                std::vector<ScArrayDimension> sc_dimensions;
+                uint32_t type_id = id;
+                auto const *type = &comp.get_type(type_id);
+                while (!type->array.empty())
+                {
+                    auto const &parent = comp.get_type(type->parent_type);
+                    // A pointer to an array is not an array itself.
+                    if (type->pointer && (!parent.pointer || parent.pointer_depth != type->pointer_depth))
+                    {
+                        break;
+                    }
+
+                    ScArrayDimension dimension;
+                    dimension.size = type->array.back();
+                    dimension.size_literal = type->array_size_literal.back();
+                    dimension.stride = comp.get_decoration(type_id, spv::DecorationArrayStride);
+                    sc_dimensions.insert(sc_dimensions.begin(), dimension);
+
+                    type_id = type->parent_type;
+                    type = &parent;
+                }
+
+                *element_type = type_id;
+                *size = sc_dimensions.size();
+                *dimensions = (ScArrayDimension *)malloc(sc_dimensions.size() * sizeof(ScArrayDimension));
+                for (size_t i = 0; i < sc_dimensions.size(); i++)
+                {
+                    (*dimensions)[i] = sc_dimensions[i];
+                }
+            } while (0);)
+    }
+
     ScInternalResult sc_internal_compiler_rename_interface_variable(const ScInternalCompilerBase *compiler, const ScResource *resources, const size_t resources_size, uint32_t location, const char *name)
     {
         INTERNAL_RESULT(do {
@@ -3,11 +3,16 @@
 #include "vendor/SPIRV-Cross/spirv_hlsl.hpp"
 #include "vendor/SPIRV-Cross/spirv_msl.hpp"
 #include "vendor/SPIRV-Cross/spirv_glsl.hpp"
+#include "vendor/SPIRV-Cross/spirv_reflect.hpp"
+#include "vendor/SPIRV-Cross/spirv_cpp.hpp"
 
 typedef void ScInternalCompilerBase;
 typedef void ScInternalCompilerHlsl;
 typedef void ScInternalCompilerMsl;
 typedef void ScInternalCompilerGlsl;
+typedef void ScInternalCompilerReflection;
+typedef void ScInternalCompilerCpp;
+typedef void ScInternalParsedIr;
 
 extern "C"
 {
@@ -27,6 +32,17 @@ extern "C"
         uint32_t work_group_size_z;
     } ScEntryPoint;
 
+    typedef struct ScExecutionModes
+    {
+        uint32_t *modes;
+        size_t modes_size;
+        uint32_t invocations;
+        uint32_t output_vertices;
+        uint32_t local_size_id_x;
+        uint32_t local_size_id_y;
+        uint32_t local_size_id_z;
+    } ScExecutionModes;
+
     typedef struct ScBufferRange
     {
         unsigned index;
@@ -153,13 +169,28 @@ extern "C"
         bool *array_size_literal;
         size_t array_size;
         spv::StorageClass storage;
+        bool pointer;
+        uint32_t pointer_depth;
+        uint32_t pointee_type;
         spirv_cross::SPIRType::ImageType image;
     } ScType;
 
+    typedef struct ScArrayDimension
+    {
+        uint32_t size;
+        bool size_literal;
+        uint32_t stride;
+    } ScArrayDimension;
+
     ScInternalResult sc_internal_get_latest_exception_message(const char **message);
 
+    ScInternalResult sc_internal_parsed_ir_new(ScInternalParsedIr **ir, const uint32_t *words, const size_t size);
+    ScInternalResult sc_internal_parsed_ir_delete(ScInternalParsedIr *ir);
+
 #ifdef SPIRV_CROSS_WRAPPER_HLSL
     ScInternalResult sc_internal_compiler_hlsl_new(ScInternalCompilerHlsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_hlsl_new_from_parsed_ir(ScInternalCompilerHlsl **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_hlsl_clone(ScInternalCompilerHlsl **clone, const ScInternalCompilerHlsl *compiler);
     ScInternalResult sc_internal_compiler_hlsl_set_options(const ScInternalCompilerHlsl *compiler, const ScHlslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_hlsl_set_root_constant_layout(const ScInternalCompilerHlsl *compiler, const ScHlslRootConstant *constants, size_t count);
 #endif
@@ -172,6 +203,8 @@ extern "C"
     } ScMslConstSamplerMapping;
 
     ScInternalResult sc_internal_compiler_msl_new(ScInternalCompilerMsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_msl_new_from_parsed_ir(ScInternalCompilerMsl **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_msl_clone(ScInternalCompilerMsl **clone, const ScInternalCompilerMsl *compiler);
     ScInternalResult sc_internal_compiler_msl_set_options(const ScInternalCompilerMsl *compiler, const ScMslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_msl_get_is_rasterization_disabled(const ScInternalCompilerMsl *compiler, bool *is_rasterization_disabled);
     ScInternalResult sc_internal_compiler_msl_compile(const ScInternalCompilerBase *compiler, const char **shader,
@@ -182,6 +215,8 @@ extern "C"
 
 #ifdef SPIRV_CROSS_WRAPPER_GLSL
     ScInternalResult sc_internal_compiler_glsl_new(ScInternalCompilerGlsl **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_glsl_new_from_parsed_ir(ScInternalCompilerGlsl **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_glsl_clone(ScInternalCompilerGlsl **clone, const ScInternalCompilerGlsl *compiler);
     ScInternalResult sc_internal_compiler_glsl_set_options(const ScInternalCompilerGlsl *compiler, const ScGlslCompilerOptions *options);
     ScInternalResult sc_internal_compiler_glsl_build_combined_image_samplers(const ScInternalCompilerBase *compiler);
     ScInternalResult sc_internal_compiler_glsl_get_combined_image_samplers(const ScInternalCompilerBase *compiler, const ScCombinedImageSampler **samplers, size_t *size);
@@ -189,13 +224,31 @@ extern "C"
     ScInternalResult sc_internal_compiler_glsl_flatten_buffer_block(const ScInternalCompilerBase *compiler, const uint32_t id);
 #endif
 
+#ifdef SPIRV_CROSS_WRAPPER_REFLECT
+    ScInternalResult sc_internal_compiler_reflection_new(ScInternalCompilerReflection **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_reflection_new_from_parsed_ir(ScInternalCompilerReflection **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_reflection_clone(ScInternalCompilerReflection **clone, const ScInternalCompilerReflection *compiler);
+#endif
+
+#ifdef SPIRV_CROSS_WRAPPER_CPP
+    ScInternalResult sc_internal_compiler_cpp_new(ScInternalCompilerCpp **compiler, const uint32_t *ir, const size_t size);
+    ScInternalResult sc_internal_compiler_cpp_new_from_parsed_ir(ScInternalCompilerCpp **compiler, const ScInternalParsedIr *ir);
+    ScInternalResult sc_internal_compiler_cpp_clone(ScInternalCompilerCpp **clone, const ScInternalCompilerCpp *compiler);
+    ScInternalResult sc_internal_compiler_cpp_set_interface_name(const ScInternalCompilerCpp *compiler, const char *name);
+#endif
+
     ScInternalResult sc_internal_compiler_get_decoration(const ScInternalCompilerBase *compiler, uint32_t *result, const uint32_t id, const spv::Decoration decoration);
     ScInternalResult sc_internal_compiler_set_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const uint32_t argument);
     ScInternalResult sc_internal_compiler_unset_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_has_decoration(const ScInternalCompilerBase *compiler, bool *result, const uint32_t id, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_get_decoration_bitset(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t **decorations, size_t *size);
+    ScInternalResult sc_internal_compiler_get_decoration_string(const ScInternalCompilerBase *compiler, const char **result, const uint32_t id, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_set_decoration_string(const ScInternalCompilerBase *compiler, const uint32_t id, const spv::Decoration decoration, const char *argument);
     ScInternalResult sc_internal_compiler_get_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char **name);
     ScInternalResult sc_internal_compiler_set_name(const ScInternalCompilerBase *compiler, const uint32_t id, const char *name);
     ScInternalResult sc_internal_compiler_set_member_name(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const char *name);
     ScInternalResult sc_internal_compiler_get_entry_points(const ScInternalCompilerBase *compiler, ScEntryPoint **entry_points, size_t *size);
+    ScInternalResult sc_internal_compiler_get_execution_modes(const ScInternalCompilerBase *compiler, const char *entry_point_name, const spv::ExecutionModel execution_model, ScExecutionModes *execution_modes);
     ScInternalResult sc_internal_compiler_get_active_buffer_ranges(const ScInternalCompilerBase *compiler, uint32_t id, ScBufferRange **active_buffer_ranges, size_t *size);
     ScInternalResult sc_internal_compiler_get_cleansed_entry_point_name(const ScInternalCompilerBase *compiler, const char *original_entry_point_name, const spv::ExecutionModel execution_model, const char **compiled_entry_point_name);
     ScInternalResult sc_internal_compiler_get_shader_resources(const ScInternalCompilerBase *compiler, ScShaderResources *shader_resources);
@@ -206,8 +259,11 @@ extern "C"
     ScInternalResult sc_internal_compiler_get_member_name(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const char **name);
     ScInternalResult sc_internal_compiler_get_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, uint32_t *result);
     ScInternalResult sc_internal_compiler_set_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration, const uint32_t argument);
+    ScInternalResult sc_internal_compiler_unset_member_decoration(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, const spv::Decoration decoration);
+    ScInternalResult sc_internal_compiler_get_member_decoration_bitset(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, uint32_t **decorations, size_t *size);
     ScInternalResult sc_internal_compiler_get_declared_struct_size(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *result);
     ScInternalResult sc_internal_compiler_get_declared_struct_member_size(const ScInternalCompilerBase *compiler, const uint32_t id, const uint32_t index, uint32_t *result);
+    ScInternalResult sc_internal_compiler_get_array_layout(const ScInternalCompilerBase *compiler, const uint32_t id, uint32_t *element_type, ScArrayDimension **dimensions, size_t *size);
     ScInternalResult sc_internal_compiler_rename_interface_variable(const ScInternalCompilerBase *compiler, const ScResource *resources, const size_t resources_size, uint32_t location, const char *name);
     ScInternalResult sc_internal_compiler_get_work_group_size_specialization_constants(const ScInternalCompilerBase *compiler, ScSpecializationConstant **constants);
     ScInternalResult sc_internal_compiler_set_entry_point(const ScInternalCompilerBase *compiler, const char *name, const spv::ExecutionModel execution_model);
@@ -0,0 +1,88 @@
+use spirv_cross::backend::{AnyAst, AnyCompilerOptions, TargetKind};
+use spirv_cross::{glsl, hlsl, msl, spirv, ErrorCode};
+
+mod common;
+use crate::common::words;
+
+#[test]
+fn target_kind_parses_names() {
+    assert_eq!("glsl".parse::<TargetKind>(), Ok(TargetKind::Glsl));
+    assert_eq!("HLSL".parse::<TargetKind>(), Ok(TargetKind::Hlsl));
+    assert_eq!("msl".parse::<TargetKind>(), Ok(TargetKind::Msl));
+    assert_eq!(
+        "spirv".parse::<TargetKind>(),
+        Err(ErrorCode::UnknownTarget(String::from("spirv")))
+    );
+    for kind in TargetKind::ALL {
+        assert_eq!(kind.to_string().parse::<TargetKind>(), Ok(*kind));
+        assert_eq!(AnyCompilerOptions::default_for(*kind).kind(), *kind);
+    }
+}
+
+#[test]
+fn any_ast_matches_typed_ast() {
+    let words = words(include_bytes!("shaders/simple.vert.spv"));
+    let module = spirv::Module::from_words(&words);
+
+    let mut glsl_options = glsl::CompilerOptions::default();
+    glsl_options.version = glsl::Version::V4_60;
+    let mut typed = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    typed.set_compiler_options(&glsl_options).unwrap();
+    let mut any = AnyAst::parse(TargetKind::Glsl, &module).unwrap();
+    any.set_compiler_options(&glsl_options.into()).unwrap();
+    assert_eq!(any.kind(), TargetKind::Glsl);
+    assert_eq!(any.compile_and_reflect(), typed.compile_and_reflect());
+
+    let mut typed = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    let mut any = AnyAst::parse(TargetKind::Hlsl, &module).unwrap();
+    any.set_compiler_options(&AnyCompilerOptions::default_for(TargetKind::Hlsl))
+        .unwrap();
+    assert_eq!(any.compile(), typed.compile());
+
+    let mut typed = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let mut any = AnyAst::from(spirv::Ast::<msl::Target>::parse(&module).unwrap());
+    assert_eq!(any.kind(), TargetKind::Msl);
+    assert_eq!(any.compile(), typed.compile());
+}
+
+#[test]
+fn any_ast_exposes_reflection() {
+    let words = words(include_bytes!("shaders/simple.vert.spv"));
+    let module = spirv::Module::from_words(&words);
+    let typed = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+
+    for kind in &[TargetKind::Glsl, TargetKind::Hlsl, TargetKind::Msl] {
+        let mut any = AnyAst::parse(*kind, &module).unwrap();
+        let resources = any.get_shader_resources().unwrap();
+        assert_eq!(resources, typed.get_shader_resources().unwrap());
+        assert_eq!(
+            any.get_entry_points().unwrap(),
+            typed.get_entry_points().unwrap()
+        );
+
+        let uniform_buffer = &resources.uniform_buffers[0];
+        assert_eq!(any.get_declared_struct_size(uniform_buffer.type_id), Ok(68));
+        any.set_decoration(uniform_buffer.id, spirv::Decoration::Binding, 3)
+            .unwrap();
+        assert_eq!(
+            any.get_decoration(uniform_buffer.id, spirv::Decoration::Binding),
+            Ok(3)
+        );
+        assert!(any.try_clone().is_ok());
+    }
+}
+
+#[test]
+fn any_ast_rejects_options_for_another_target() {
+    let words = words(include_bytes!("shaders/simple.vert.spv"));
+    let module = spirv::Module::from_words(&words);
+    let mut any = AnyAst::parse(TargetKind::Msl, &module).unwrap();
+
+    assert_eq!(
+        any.set_compiler_options(&glsl::CompilerOptions::default().into()),
+        Err(ErrorCode::TargetMismatch {
+            expected: TargetKind::Msl,
+            found: TargetKind::Glsl,
+        })
+    );
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "batch")]
+
+use spirv_cross::batch::{self, Batch, Job, Target};
+use spirv_cross::{glsl, hlsl, msl, spirv, ErrorCode};
+
+mod common;
+use crate::common::words;
+
+fn compile_serially(job: &Job) -> Result<spirv::CompiledShader, ErrorCode> {
+    match &job.target {
+        Target::Glsl(options) => {
+            let mut ast = spirv::Ast::<glsl::Target>::parse(&job.module)?;
+            ast.set_compiler_options(options)?;
+            ast.compile_and_reflect()
+        }
+        Target::Hlsl(options) => {
+            let mut ast = spirv::Ast::<hlsl::Target>::parse(&job.module)?;
+            ast.set_compiler_options(options)?;
+            ast.compile_and_reflect()
+        }
+        Target::Msl(options) => {
+            let mut ast = spirv::Ast::<msl::Target>::parse(&job.module)?;
+            ast.set_compiler_options(options)?;
+            ast.compile_and_reflect()
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn batch_returns_results_in_job_order() {
+    let vertex = words(include_bytes!("shaders/simple.vert.spv"));
+    let fragment = words(include_bytes!("shaders/struct.frag.spv"));
+    let compute = words(include_bytes!("shaders/workgroup.comp.spv"));
+
+    let mut jobs = Vec::new();
+    for module in &[&vertex, &fragment, &compute] {
+        for version in &[glsl::Version::V3_30, glsl::Version::V4_60] {
+            let mut options = glsl::CompilerOptions::default();
+            options.version = *version;
+            jobs.push(Job::new(
+                spirv::Module::from_words(module),
+                Target::Glsl(options),
+            ));
+        }
+        jobs.push(Job::new(
+            spirv::Module::from_words(module),
+            Target::Hlsl(hlsl::CompilerOptions::default()),
+        ));
+        jobs.push(Job::new(
+            spirv::Module::from_words(module),
+            Target::Msl(msl::CompilerOptions::default()),
+        ));
+    }
+
+    let expected = jobs.iter().map(compile_serially).collect::<Vec<_>>();
+    assert_eq!(Batch::new().with_threads(3).compile(&jobs), expected);
+    assert_eq!(Batch::new().with_threads(1).compile(&jobs), expected);
+    assert_eq!(batch::compile(&jobs), expected);
+}
+
+#[test]
+fn batch_reuses_parsed_module_with_different_options() {
+    let module = words(include_bytes!("shaders/simple.vert.spv"));
+    let jobs = [
+        glsl::Version::V3_30,
+        glsl::Version::V4_60,
+        glsl::Version::V3_30,
+    ]
+    .iter()
+    .map(|version| {
+        let mut options = glsl::CompilerOptions::default();
+        options.version = *version;
+        Job::new(spirv::Module::from_words(&module), Target::Glsl(options))
+    })
+    .collect::<Vec<_>>();
+
+    let sources = Batch::new()
+        .with_threads(1)
+        .compile(&jobs)
+        .into_iter()
+        .map(|result| result.unwrap().source)
+        .collect::<Vec<_>>();
+    assert!(sources[0].starts_with("#version 330"));
+    assert!(sources[1].starts_with("#version 460"));
+    assert_eq!(sources[2], sources[0]);
+}
+
+#[test]
+fn batch_reports_errors_per_job() {
+    let valid = words(include_bytes!("shaders/simple.vert.spv"));
+    let invalid = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 16, 0, 0xffff_ffff];
+
+    let jobs = vec![
+        Job::new(
+            spirv::Module::from_words(&invalid).with_validation(),
+            Target::Glsl(glsl::CompilerOptions::default()),
+        ),
+        Job::new(
+            spirv::Module::from_words(&valid).with_validation(),
+            Target::Glsl(glsl::CompilerOptions::default()),
+        ),
+    ];
+
+    let results = Batch::new().with_threads(2).compile(&jobs);
+    assert!(
+        matches!(results[0], Err(ErrorCode::Validation { .. })),
+        "{:?}",
+        results[0]
+    );
+    assert_eq!(results[1], compile_serially(&jobs[1]));
+}
+
+#[test]
+fn batch_compiles_empty_job_list() {
+    assert!(Batch::new().compile(&[]).is_empty());
+}
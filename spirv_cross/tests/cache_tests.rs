@@ -0,0 +1,329 @@
+#![cfg(feature = "cache")]
+
+use spirv_cross::cache::{Cache, Key};
+use spirv_cross::spirv::{self, CompiledShader};
+use spirv_cross::{glsl, hlsl, msl, ErrorCode};
+use std::path::PathBuf;
+
+fn module_words() -> Vec<u32> {
+    spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words()
+}
+
+/// Creates an empty directory for a test.
+fn test_directory(name: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join("cache_tests")
+        .join(name);
+    let _ = std::fs::remove_dir_all(&directory);
+    directory
+}
+
+fn shader(source: &str) -> CompiledShader {
+    CompiledShader {
+        source: String::from(source),
+        entry_points: vec![spirv::EntryPoint {
+            name: String::from("main"),
+            execution_model: spirv::ExecutionModel::Vertex,
+            work_group_size: spirv::WorkGroupSize { x: 0, y: 0, z: 0 },
+        }],
+        shader_resources: spirv::ShaderResources {
+            uniform_buffers: vec![spirv::Resource {
+                id: 1,
+                type_id: 2,
+                base_type_id: 3,
+                name: String::from("uniform_buffer_object"),
+            }],
+            storage_buffers: Vec::new(),
+            stage_inputs: Vec::new(),
+            stage_outputs: Vec::new(),
+            subpass_inputs: Vec::new(),
+            storage_images: Vec::new(),
+            sampled_images: Vec::new(),
+            atomic_counters: Vec::new(),
+            push_constant_buffers: Vec::new(),
+            separate_images: Vec::new(),
+            separate_samplers: Vec::new(),
+        },
+        specialization_constants: vec![spirv::SpecializationConstant {
+            id: 4,
+            constant_id: 5,
+        }],
+    }
+}
+
+#[test]
+fn key_depends_on_module_and_target() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let mut other_words = words.clone();
+    *other_words.last_mut().unwrap() ^= 1;
+    let other_module = spirv::Module::from_words(&other_words);
+
+    let glsl_options = glsl::CompilerOptions::default();
+    assert_eq!(
+        Key::glsl(&module, &glsl_options),
+        Key::glsl(&spirv::Module::from_words(&words.clone()), &glsl_options)
+    );
+    assert_ne!(
+        Key::glsl(&module, &glsl_options),
+        Key::glsl(&other_module, &glsl_options)
+    );
+    assert_ne!(
+        Key::hlsl(&module, &hlsl::CompilerOptions::default(), &[]),
+        Key::msl(&module, &msl::CompilerOptions::default())
+    );
+    assert_eq!(Key::glsl(&module, &glsl_options).to_string().len(), 64);
+}
+
+#[test]
+fn key_depends_on_options() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+
+    let glsl_options = glsl::CompilerOptions::default();
+    let mut other_glsl_options = glsl_options.clone();
+    other_glsl_options.vertex.invert_y = true;
+    assert_ne!(
+        Key::glsl(&module, &glsl_options),
+        Key::glsl(&module, &other_glsl_options)
+    );
+
+    let hlsl_options = hlsl::CompilerOptions::default();
+    let mut other_hlsl_options = hlsl_options.clone();
+    other_hlsl_options.entry_point = Some((String::from("main"), spirv::ExecutionModel::Vertex));
+    assert_ne!(
+        Key::hlsl(&module, &hlsl_options, &[]),
+        Key::hlsl(&module, &other_hlsl_options, &[])
+    );
+}
+
+#[test]
+fn key_depends_on_hlsl_root_constant_layout() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let options = hlsl::CompilerOptions::default();
+    let layout = hlsl::RootConstant {
+        start: 0,
+        end: 16,
+        binding: 0,
+        space: 0,
+    };
+
+    assert_ne!(
+        Key::hlsl(&module, &options, &[]),
+        Key::hlsl(&module, &options, &[layout])
+    );
+    assert_ne!(
+        Key::hlsl(&module, &options, &[layout]),
+        Key::hlsl(
+            &module,
+            &options,
+            &[hlsl::RootConstant { space: 1, ..layout }]
+        )
+    );
+}
+
+#[test]
+fn key_depends_on_msl_overrides() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let options = msl::CompilerOptions::default();
+
+    let mut resource_binding = options.clone();
+    resource_binding.resource_binding_overrides.insert(
+        msl::ResourceBindingLocation {
+            stage: spirv::ExecutionModel::Vertex,
+            desc_set: 0,
+            binding: 0,
+        },
+        msl::ResourceBinding {
+            buffer_id: 5,
+            texture_id: 6,
+            sampler_id: 7,
+            count: 0,
+        },
+    );
+
+    let mut vertex_attribute = options.clone();
+    vertex_attribute.vertex_attribute_overrides.insert(
+        msl::VertexAttributeLocation(0),
+        msl::VertexAttribute {
+            buffer_id: 1,
+            format: msl::Format::Other,
+            built_in: None,
+            vecsize: 4,
+        },
+    );
+
+    let mut const_sampler = options.clone();
+    const_sampler.const_samplers.insert(
+        msl::SamplerLocation {
+            desc_set: 0,
+            binding: 1,
+        },
+        msl::SamplerData {
+            coord: msl::SamplerCoord::Normalized,
+            min_filter: msl::SamplerFilter::Linear,
+            mag_filter: msl::SamplerFilter::Linear,
+            mip_filter: msl::SamplerMipFilter::None,
+            s_address: msl::SamplerAddress::Repeat,
+            t_address: msl::SamplerAddress::Repeat,
+            r_address: msl::SamplerAddress::Repeat,
+            compare_func: msl::SamplerCompareFunc::Never,
+            border_color: msl::SamplerBorderColor::TransparentBlack,
+            lod_clamp_min: msl::LodBase16::ZERO,
+            lod_clamp_max: msl::LodBase16::MAX,
+            max_anisotropy: 1,
+            planes: 0,
+            resolution: msl::FormatResolution::_444,
+            chroma_filter: msl::SamplerFilter::Nearest,
+            x_chroma_offset: msl::ChromaLocation::CositedEven,
+            y_chroma_offset: msl::ChromaLocation::CositedEven,
+            swizzle: [msl::ComponentSwizzle::Identity; 4],
+            ycbcr_conversion_enable: false,
+            ycbcr_model: msl::SamplerYCbCrModelConversion::RgbIdentity,
+            ycbcr_range: msl::SamplerYCbCrRange::ItuFull,
+            bpc: 8,
+        },
+    );
+
+    let keys = [
+        Key::msl(&module, &options),
+        Key::msl(&module, &resource_binding),
+        Key::msl(&module, &vertex_attribute),
+        Key::msl(&module, &const_sampler),
+    ];
+    for (i, a) in keys.iter().enumerate() {
+        for b in &keys[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
+
+#[test]
+fn cache_in_memory_returns_stored_results() {
+    let words = module_words();
+    let key = Key::glsl(
+        &spirv::Module::from_words(&words),
+        &glsl::CompilerOptions::default(),
+    );
+    let cache = Cache::in_memory();
+    assert_eq!(cache.get(&key), None);
+
+    cache.insert(key, shader("first")).unwrap();
+    assert_eq!(cache.get(&key), Some(shader("first")));
+    assert_eq!(
+        cache.get_or_compile(key, || panic!("result should be cached")),
+        Ok(shader("first"))
+    );
+
+    cache.clear_memory();
+    assert_eq!(cache.get(&key), None);
+}
+
+#[test]
+fn cache_does_not_store_errors() {
+    let words = module_words();
+    let key = Key::glsl(
+        &spirv::Module::from_words(&words),
+        &glsl::CompilerOptions::default(),
+    );
+    let cache = Cache::in_memory();
+
+    assert_eq!(
+        cache.get_or_compile(key, || Err(ErrorCode::Unhandled)),
+        Err(ErrorCode::Unhandled)
+    );
+    assert_eq!(
+        cache.get_or_compile(key, || Ok(shader("compiled"))),
+        Ok(shader("compiled"))
+    );
+}
+
+#[test]
+fn cache_in_directory_is_shared_between_caches() {
+    let directory = test_directory("shared");
+    let words = module_words();
+    let key = Key::glsl(
+        &spirv::Module::from_words(&words),
+        &glsl::CompilerOptions::default(),
+    );
+
+    Cache::in_directory(&directory)
+        .insert(key, shader("stored"))
+        .unwrap();
+    assert!(directory.join(format!("{}.bin", key)).is_file());
+
+    let cache = Cache::in_memory().with_directory(&directory);
+    assert_eq!(cache.get(&key), Some(shader("stored")));
+
+    // The entry was also kept in memory.
+    std::fs::remove_dir_all(&directory).unwrap();
+    assert_eq!(cache.get(&key), Some(shader("stored")));
+    assert_eq!(Cache::in_directory(&directory).get(&key), None);
+}
+
+#[test]
+fn cache_in_directory_ignores_corrupt_entries() {
+    let directory = test_directory("corrupt");
+    let words = module_words();
+    let key = Key::glsl(
+        &spirv::Module::from_words(&words),
+        &glsl::CompilerOptions::default(),
+    );
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(directory.join(format!("{}.bin", key)), b"corrupt").unwrap();
+
+    let cache = Cache::in_directory(&directory);
+    assert_eq!(cache.get(&key), None);
+    assert_eq!(
+        cache.get_or_compile(key, || Ok(shader("recompiled"))),
+        Ok(shader("recompiled"))
+    );
+    assert_eq!(
+        Cache::in_directory(&directory).get(&key),
+        Some(shader("recompiled"))
+    );
+}
+
+#[test]
+fn cache_compiles_on_miss() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let cache = Cache::in_memory();
+
+    let options = glsl::CompilerOptions::default();
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let expected = ast.compile_and_reflect().unwrap();
+    assert_eq!(cache.compile_glsl(&module, &options), Ok(expected.clone()));
+    assert_eq!(cache.get(&Key::glsl(&module, &options)), Some(expected));
+
+    let layout = [hlsl::RootConstant {
+        start: 0,
+        end: 16,
+        binding: 0,
+        space: 0,
+    }];
+    let compiled = cache
+        .compile_hlsl(&module, &hlsl::CompilerOptions::default(), &layout)
+        .unwrap();
+    assert_eq!(
+        cache.get(&Key::hlsl(
+            &module,
+            &hlsl::CompilerOptions::default(),
+            &layout
+        )),
+        Some(compiled)
+    );
+
+    let compiled = cache
+        .compile_msl(&module, &msl::CompilerOptions::default())
+        .unwrap();
+    assert_eq!(
+        cache.get(&Key::msl(&module, &msl::CompilerOptions::default())),
+        Some(compiled)
+    );
+}
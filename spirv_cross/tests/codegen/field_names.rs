@@ -0,0 +1,13 @@
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Names {
+    pub foo_bar: f32,
+    pub foo_bar_1: f32,
+    pub _pad1: [u8; 8],
+    pub _pad0: f32,
+}
+
+const _: () = assert!(::core::mem::size_of::<Names>() == 20);
+const _: () = assert!(::core::mem::offset_of!(Names, foo_bar) == 0);
+const _: () = assert!(::core::mem::offset_of!(Names, foo_bar_1) == 4);
+const _: () = assert!(::core::mem::offset_of!(Names, _pad0) == 16);
@@ -0,0 +1,28 @@
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ParamsWeightsElement {
+    pub value: f32,
+    pub _pad0: [u8; 12],
+}
+
+const _: () = assert!(::core::mem::size_of::<ParamsWeightsElement>() == 16);
+const _: () = assert!(::core::mem::offset_of!(ParamsWeightsElement, value) == 0);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Params {
+    pub scale: f32,
+    pub _pad0: [u8; 12],
+    pub light_dir: [f32; 3],
+    pub _pad1: [u8; 4],
+    pub weights: [ParamsWeightsElement; 2],
+    pub normal_matrix: [[f32; 4]; 3],
+    pub size: [i32; 2],
+}
+
+const _: () = assert!(::core::mem::size_of::<Params>() == 120);
+const _: () = assert!(::core::mem::offset_of!(Params, scale) == 0);
+const _: () = assert!(::core::mem::offset_of!(Params, light_dir) == 16);
+const _: () = assert!(::core::mem::offset_of!(Params, weights) == 32);
+const _: () = assert!(::core::mem::offset_of!(Params, normal_matrix) == 64);
+const _: () = assert!(::core::mem::offset_of!(Params, size) == 112);
@@ -0,0 +1,34 @@
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Light {
+    pub position: [f32; 3],
+    pub radius: f32,
+    pub basis: [[f32; 2]; 2],
+}
+
+const _: () = assert!(::core::mem::size_of::<Light>() == 32);
+const _: () = assert!(::core::mem::offset_of!(Light, position) == 0);
+const _: () = assert!(::core::mem::offset_of!(Light, radius) == 12);
+const _: () = assert!(::core::mem::offset_of!(Light, basis) == 16);
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct SceneHeader {
+    pub view: [[f32; 4]; 4],
+    pub weights: [[[f32; 4]; 3]; 2],
+    pub lights: [Light; 4],
+}
+
+const _: () = assert!(::core::mem::size_of::<SceneHeader>() == 288);
+const _: () = assert!(::core::mem::offset_of!(SceneHeader, view) == 0);
+const _: () = assert!(::core::mem::offset_of!(SceneHeader, weights) == 64);
+const _: () = assert!(::core::mem::offset_of!(SceneHeader, lights) == 160);
+
+pub type SceneValuesElement = f32;
+
+impl SceneHeader {
+    /// Offset in bytes of the first element of `values`.
+    pub const VALUES_OFFSET: usize = 288;
+    /// Bytes between consecutive elements of `values`.
+    pub const VALUES_STRIDE: usize = 4;
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "codegen")]
+
+use spirv_cross::{codegen, glsl};
+use std::{env, fs, path::Path};
+
+mod common;
+use crate::common::ast;
+
+// The expected output of each shader is checked in under `tests/codegen` and included below, so
+// that the layout assertions it contains are compiled along with the tests. Run the tests with
+// `UPDATE_CODEGEN_FIXTURES=1` to regenerate it.
+#[allow(dead_code)]
+mod generated {
+    pub mod std140_block {
+        include!("codegen/std140_block.rs");
+    }
+
+    pub mod struct_layout {
+        include!("codegen/struct_layout.rs");
+    }
+
+    pub mod field_names {
+        include!("codegen/field_names.rs");
+    }
+}
+
+fn check_fixture(name: &str, source: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/codegen")
+        .join(name);
+    if env::var_os("UPDATE_CODEGEN_FIXTURES").is_some() {
+        fs::write(&path, source).unwrap();
+    } else {
+        assert_eq!(source, fs::read_to_string(&path).unwrap());
+    }
+}
+
+#[test]
+fn generates_padded_structs() {
+    let mut ast = ast::<glsl::Target>(include_bytes!("shaders/std140_block.asm.spv"));
+    let source = codegen::generate(&mut ast).unwrap();
+
+    check_fixture("std140_block.rs", &source);
+    assert_eq!(
+        ::core::mem::size_of::<generated::std140_block::Params>(),
+        120
+    );
+}
+
+#[test]
+fn generates_runtime_array_header_and_element() {
+    let mut ast = ast::<glsl::Target>(include_bytes!("shaders/struct_layout.asm.spv"));
+    let source = codegen::generate(&mut ast).unwrap();
+
+    check_fixture("struct_layout.rs", &source);
+    assert_eq!(generated::struct_layout::SceneHeader::VALUES_OFFSET, 288);
+}
+
+#[test]
+fn generates_unique_field_names() {
+    let mut ast = ast::<glsl::Target>(include_bytes!("shaders/field_names.asm.spv"));
+    let source = codegen::generate(&mut ast).unwrap();
+
+    check_fixture("field_names.rs", &source);
+}
+
+#[test]
+fn generates_buffer_references_as_device_addresses() {
+    let mut ast = ast::<glsl::Target>(include_bytes!("shaders/buffer_reference.asm.spv"));
+    let source = codegen::generate(&mut ast).unwrap();
+
+    assert!(source.contains("pub struct Push {\n    pub root: u64,\n    pub count: u64,\n}"));
+    assert!(source.contains("assert!(::core::mem::size_of::<Push>() == 16);"));
+    assert!(!source.contains("struct Node"));
+}
@@ -1,4 +1,6 @@
-#[allow(clippy::cast_ptr_alignment)]
+use spirv_cross::spirv;
+
+#[allow(dead_code, clippy::cast_ptr_alignment)]
 pub fn words_from_bytes(buf: &[u8]) -> &[u32] {
     unsafe {
         std::slice::from_raw_parts(
@@ -7,3 +9,20 @@ pub fn words_from_bytes(buf: &[u8]) -> &[u32] {
         )
     }
 }
+
+/// Reads the words of a module stored in either byte order.
+#[allow(dead_code)]
+pub fn words(bytes: &[u8]) -> Vec<u32> {
+    spirv::ModuleBuf::from_bytes(bytes).unwrap().into_words()
+}
+
+/// Parses a module stored in either byte order.
+#[allow(dead_code)]
+pub fn ast<TTarget>(bytes: &[u8]) -> spirv::Ast<TTarget>
+where
+    TTarget: spirv::Target,
+    spirv::Ast<TTarget>: spirv::Parse<TTarget> + spirv::Compile<TTarget>,
+{
+    let words = words(bytes);
+    spirv::Ast::<TTarget>::parse(&spirv::Module::from_words(&words)).unwrap()
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "cpp")]
+
+use spirv_cross::cpp;
+
+mod common;
+use crate::common::ast;
+
+#[test]
+fn cpp_compiler_options_has_default() {
+    let compiler_options = cpp::CompilerOptions::default();
+    assert_eq!(compiler_options.interface_name, None);
+    assert_eq!(compiler_options.entry_point, None);
+}
+
+#[test]
+fn ast_compiles_to_cpp() {
+    let mut ast = ast::<cpp::Target>(include_bytes!("shaders/workgroup.comp.spv"));
+    ast.set_compiler_options(&cpp::CompilerOptions::default())
+        .unwrap();
+    let source = ast.compile().unwrap();
+
+    assert!(
+        source.contains("#include \"spirv_cross/internal_interface.hpp\""),
+        "{}",
+        source
+    );
+    assert!(source.contains("void main()"), "{}", source);
+}
+
+#[test]
+fn ast_compiles_to_cpp_with_interface_name() {
+    let mut ast = ast::<cpp::Target>(include_bytes!("shaders/workgroup.comp.spv"));
+    let mut options = cpp::CompilerOptions::default();
+    options.interface_name = Some(String::from("workgroup_shader"));
+    ast.set_compiler_options(&options).unwrap();
+
+    let mut clone = ast.try_clone().unwrap();
+    let source = ast.compile().unwrap();
+    assert!(source.contains("workgroup_shader"), "{}", source);
+    assert_eq!(clone.compile().unwrap(), source);
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "derive")]
+
+use spirv_cross::glsl;
+use spirv_cross::spirv::{LayoutMismatch, ShaderBlock};
+
+mod common;
+use crate::common::ast;
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct ParamsWeightsElement {
+    value: f32,
+    _pad0: [u8; 12],
+}
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct Params {
+    scale: f32,
+    _pad0: [u8; 12],
+    light_dir: [f32; 3],
+    _pad1: [u8; 4],
+    weights: [ParamsWeightsElement; 2],
+    normal_matrix: [[f32; 4]; 3],
+    size: [i32; 2],
+}
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct UnpaddedParams {
+    scale: f32,
+    light_dir: [f32; 3],
+    weights: [f32; 2],
+    normal_matrix: [[f32; 3]; 3],
+    extra: u32,
+}
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct Light {
+    position: [f32; 3],
+    radius: f32,
+    basis: [[f32; 2]; 2],
+}
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct RenamedLight {
+    position: [f32; 3],
+    range: f32,
+    basis: [[f32; 2]; 2],
+}
+
+#[repr(C)]
+#[derive(ShaderBlock)]
+struct Scene<L: ShaderBlock> {
+    view: [[f32; 4]; 4],
+    weights: [[[f32; 4]; 3]; 2],
+    #[shader_block(nested)]
+    lights: [L; 4],
+}
+
+#[test]
+fn derive_describes_fields() {
+    let fields = Params::fields()
+        .into_iter()
+        .map(|field| (field.name, field.offset, field.size, field.array_strides))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        fields,
+        [
+            ("scale", 0, 4, vec![]),
+            ("_pad0", 4, 12, vec![1]),
+            ("light_dir", 16, 12, vec![4]),
+            ("_pad1", 28, 4, vec![1]),
+            ("weights", 32, 32, vec![16]),
+            ("normal_matrix", 64, 48, vec![4, 16]),
+            ("size", 112, 8, vec![4]),
+        ]
+    );
+
+    let lights = &Scene::<Light>::fields()[2];
+    assert_eq!(lights.array_strides, [32]);
+    assert_eq!(
+        lights.fields.map(|fields| fields().len()),
+        Some(3),
+        "nested fields are described"
+    );
+}
+
+#[test]
+fn matching_block_has_no_mismatches() {
+    let ast = ast::<glsl::Target>(include_bytes!("shaders/std140_block.asm.spv"));
+    let resources = ast.get_shader_resources().unwrap();
+
+    assert_eq!(
+        ast.check_block_layout::<Params>(&resources.uniform_buffers[0])
+            .unwrap(),
+        []
+    );
+}
+
+#[test]
+fn reports_each_mismatch() {
+    let ast = ast::<glsl::Target>(include_bytes!("shaders/std140_block.asm.spv"));
+    let resources = ast.get_shader_resources().unwrap();
+    let field = |name: &str| name.to_owned();
+
+    assert_eq!(
+        ast.check_block_layout::<UnpaddedParams>(&resources.uniform_buffers[0])
+            .unwrap(),
+        [
+            LayoutMismatch::BlockSize {
+                expected: 120,
+                actual: 64
+            },
+            LayoutMismatch::Offset {
+                field: field("light_dir"),
+                expected: 16,
+                actual: 4
+            },
+            LayoutMismatch::Offset {
+                field: field("weights"),
+                expected: 32,
+                actual: 16
+            },
+            LayoutMismatch::Size {
+                field: field("weights"),
+                expected: 32,
+                actual: 8
+            },
+            LayoutMismatch::ArrayStride {
+                field: field("weights"),
+                dimension: 0,
+                expected: 16,
+                actual: 4
+            },
+            LayoutMismatch::Offset {
+                field: field("normal_matrix"),
+                expected: 64,
+                actual: 24
+            },
+            LayoutMismatch::Size {
+                field: field("normal_matrix"),
+                expected: 48,
+                actual: 36
+            },
+            LayoutMismatch::MatrixStride {
+                field: field("normal_matrix"),
+                expected: 16,
+                actual: 12
+            },
+            LayoutMismatch::MissingField {
+                member: field("size")
+            },
+            LayoutMismatch::UnexpectedField {
+                field: field("extra")
+            },
+        ]
+    );
+}
+
+#[test]
+fn checks_nested_structs() {
+    let ast = ast::<glsl::Target>(include_bytes!("shaders/struct_layout.asm.spv"));
+    let resources = ast.get_shader_resources().unwrap();
+    let scene = &resources.storage_buffers[0];
+
+    assert_eq!(ast.check_block_layout::<Scene<Light>>(scene).unwrap(), []);
+    assert_eq!(
+        ast.check_block_layout::<Scene<RenamedLight>>(scene)
+            .unwrap(),
+        [
+            LayoutMismatch::MissingField {
+                member: "lights.radius".to_owned()
+            },
+            LayoutMismatch::UnexpectedField {
+                field: "lights.range".to_owned()
+            },
+        ]
+    );
+}
@@ -0,0 +1,52 @@
+//! Replays inputs through the same setup as the fuzz targets in `fuzz/`. Malformed modules abort
+//! the process unless SPIRV-Cross errors are returned, so these tests need `exceptions`.
+//!
+//! No crash found by the fuzzer has been committed yet. The `seed-*.bin` files in
+//! `fuzz/regressions` are hand-written malformed modules, not minimized fuzzer artifacts.
+#![cfg(all(
+    feature = "exceptions",
+    feature = "glsl",
+    feature = "hlsl",
+    feature = "msl"
+))]
+
+use std::fs;
+use std::path::Path;
+
+#[path = "../fuzz/src/lib.rs"]
+mod fuzz;
+
+/// Replays every file with the given extension, returning how many were replayed.
+fn replay_dir(dir: &Path, extension: &str) -> usize {
+    let mut replayed = 0;
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() != Some(extension.as_ref()) {
+            continue;
+        }
+        let data = fs::read(&path).unwrap();
+        fuzz::glsl(&data);
+        fuzz::hlsl(&data);
+        fuzz::msl(&data);
+        replayed += 1;
+    }
+    replayed
+}
+
+#[test]
+fn fuzz_regressions_do_not_crash() {
+    let replayed = replay_dir(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("fuzz/regressions"),
+        "bin",
+    );
+    assert!(replayed > 0);
+}
+
+#[test]
+fn fuzz_seed_corpus_does_not_crash() {
+    let replayed = replay_dir(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/shaders"),
+        "spv",
+    );
+    assert!(replayed > 0);
+}
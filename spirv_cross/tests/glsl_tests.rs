@@ -411,3 +411,47 @@ void main()
         assert_eq!(&ast.compile().unwrap(), expected_result);
     }
 }
+
+#[test]
+fn ast_try_clone_keeps_options_and_header_lines() {
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&spirv::Module::from_words(words_from_bytes(
+        include_bytes!("shaders/sampler.frag.spv"),
+    )))
+    .unwrap();
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V3_00Es;
+    ast.set_compiler_options(&options).unwrap();
+    ast.add_header_line("// Comment").unwrap();
+
+    let mut clone = ast.try_clone().unwrap();
+    clone.add_header_line("// Clone").unwrap();
+
+    let compiled = ast.compile().unwrap();
+    assert!(compiled.starts_with("#version 300 es\n// Comment\n"));
+    assert!(!compiled.contains("// Clone"));
+    assert!(clone
+        .compile()
+        .unwrap()
+        .starts_with("#version 300 es\n// Comment\n// Clone\n"));
+}
+
+#[test]
+fn ast_try_clone_after_compile_uses_new_options() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V4_60;
+    ast.set_compiler_options(&options).unwrap();
+    assert!(ast.compile().unwrap().starts_with("#version 460\n"));
+
+    let mut clone = ast.try_clone().unwrap();
+    options.version = glsl::Version::V3_00Es;
+    clone.set_compiler_options(&options).unwrap();
+    let compiled = clone.compile().unwrap();
+
+    let mut fresh = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    fresh.set_compiler_options(&options).unwrap();
+    assert!(compiled.starts_with("#version 300 es\n"));
+    assert_eq!(compiled, fresh.compile().unwrap());
+}
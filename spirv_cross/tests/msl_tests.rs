@@ -577,3 +577,70 @@ fragment main_fs_out main_fs()
         assert_eq!(&ast.compile().unwrap(), expected_result);
     }
 }
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn ast_fails_to_compile_argument_buffers_before_msl_2() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/sampler.frag.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let mut compiler_options = msl::CompilerOptions::default();
+    compiler_options.version = spirv_cross::msl::Version::V1_2;
+    compiler_options.enable_argument_buffers = true;
+    ast.set_compiler_options(&compiler_options).unwrap();
+    match ast.compile() {
+        Err(spirv_cross::ErrorCode::CompilationError(message)) => assert!(!message.is_empty()),
+        _ => panic!("expected a compilation error"),
+    }
+}
+
+#[test]
+fn ast_try_clone_keeps_options() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let mut compiler_options = msl::CompilerOptions::default();
+    compiler_options.capture_output_to_buffer = true;
+    compiler_options.output_buffer_index = 456;
+    ast.set_compiler_options(&compiler_options).unwrap();
+
+    let mut clone = ast.try_clone().unwrap();
+    let compiled = clone.compile().unwrap();
+    assert!(compiled.contains("device main0_out* spvOut [[buffer(456)]]"));
+    assert_eq!(compiled, ast.compile().unwrap());
+}
+
+#[test]
+fn resource_binding_overrides_accept_mesh_and_task_stages() {
+    let shaders: [(&[u8], spirv::ExecutionModel); 2] = [
+        (
+            include_bytes!("shaders/task.asm.spv"),
+            spirv::ExecutionModel::TaskNv,
+        ),
+        (
+            include_bytes!("shaders/mesh.asm.spv"),
+            spirv::ExecutionModel::MeshNv,
+        ),
+    ];
+    for (bytes, execution_model) in shaders.iter() {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+
+        let mut compiler_options = msl::CompilerOptions::default();
+        compiler_options.entry_point = Some((String::from("main"), *execution_model));
+        compiler_options.resource_binding_overrides.insert(
+            msl::ResourceBindingLocation {
+                stage: *execution_model,
+                desc_set: 0,
+                binding: 0,
+            },
+            msl::ResourceBinding {
+                buffer_id: 1,
+                texture_id: 0,
+                sampler_id: 0,
+                count: 0,
+            },
+        );
+        ast.set_compiler_options(&compiler_options).unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "reflect")]
+
+use spirv_cross::reflect;
+
+mod common;
+use crate::common::ast;
+
+#[test]
+fn ast_compiles_to_json_reflection() {
+    let mut ast = ast::<reflect::Target>(include_bytes!("shaders/simple.vert.spv"));
+    ast.set_compiler_options(&reflect::CompilerOptions::default())
+        .unwrap();
+    let json = ast.compile().unwrap();
+
+    assert!(json.starts_with("{\n    \"entryPoints\" : ["), "{}", json);
+    assert!(json.contains("\"mode\" : \"vert\""), "{}", json);
+    assert!(json.contains("\"name\" : \"a_position\""), "{}", json);
+    assert!(
+        json.contains("\"name\" : \"uniform_buffer_object\""),
+        "{}",
+        json
+    );
+    assert!(json.trim_end().ends_with('}'), "{}", json);
+}
+
+#[test]
+fn ast_reflects_specialization_constants() {
+    let mut ast = ast::<reflect::Target>(include_bytes!("shaders/specialization.comp.spv"));
+    let json = ast.compile().unwrap();
+
+    assert!(json.contains("\"mode\" : \"comp\""), "{}", json);
+    assert!(json.contains("\"specialization_constants\""), "{}", json);
+}
+
+#[test]
+fn ast_reflection_can_be_cloned() {
+    let mut ast = ast::<reflect::Target>(include_bytes!("shaders/struct.frag.spv"));
+    let mut clone = ast.try_clone().unwrap();
+
+    assert_eq!(clone.compile().unwrap(), ast.compile().unwrap());
+}
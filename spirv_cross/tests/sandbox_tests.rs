@@ -0,0 +1,190 @@
+#![cfg(feature = "sandbox")]
+
+use spirv_cross::backend::AnyCompilerOptions;
+use spirv_cross::sandbox::Sandbox;
+use spirv_cross::{glsl, hlsl, msl, spirv, ErrorCode};
+
+fn helper() -> Sandbox {
+    Sandbox::new(env!("CARGO_BIN_EXE_spirv-cross-sandbox"))
+}
+
+fn module_words() -> Vec<u32> {
+    spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words()
+}
+
+#[test]
+fn sandbox_matches_in_process_glsl() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let mut options = glsl::CompilerOptions::default();
+    options.version = glsl::Version::V3_10Es;
+
+    let mut ast = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let expected = ast.compile_and_reflect().unwrap();
+
+    let compiled = helper()
+        .compile(&module, &AnyCompilerOptions::Glsl(options))
+        .unwrap();
+    assert_eq!(compiled, expected);
+    assert!(compiled.source.starts_with("#version 310 es"));
+}
+
+#[test]
+fn sandbox_matches_in_process_hlsl() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let mut options = hlsl::CompilerOptions::default();
+    options.shader_model = hlsl::ShaderModel::V6_0;
+
+    let mut ast = spirv::Ast::<hlsl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let expected = ast.compile_and_reflect().unwrap();
+
+    let compiled = helper()
+        .compile(&module, &AnyCompilerOptions::Hlsl(options))
+        .unwrap();
+    assert_eq!(compiled, expected);
+}
+
+#[test]
+fn sandbox_matches_in_process_msl() {
+    let words = module_words();
+    let module = spirv::Module::from_words(&words);
+    let mut options = msl::CompilerOptions::default();
+    options.vertex_attribute_overrides.insert(
+        msl::VertexAttributeLocation(1),
+        msl::VertexAttribute {
+            buffer_id: 2,
+            format: msl::Format::Other,
+            built_in: None,
+            vecsize: 3,
+        },
+    );
+
+    let mut ast = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    ast.set_compiler_options(&options).unwrap();
+    let expected = ast.compile_and_reflect().unwrap();
+
+    let compiled = helper()
+        .compile(&module, &AnyCompilerOptions::Msl(options))
+        .unwrap();
+    assert_eq!(compiled, expected);
+}
+
+#[test]
+fn sandbox_reports_invalid_module() {
+    let words = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 16, 0, 0xffff_ffff];
+    let module = spirv::Module::from_words(&words);
+
+    let error = helper()
+        .compile(
+            &module,
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    if cfg!(feature = "exceptions") {
+        assert!(matches!(error, ErrorCode::InvalidModule(_)), "{:?}", error);
+    } else {
+        assert!(matches!(error, ErrorCode::SandboxAborted(_)), "{:?}", error);
+    }
+}
+
+#[test]
+fn sandbox_runs_requested_validation_in_host() {
+    let words = [spirv::MAGIC_NUMBER, 0x0001_0000, 0, 16, 0, 0xffff_ffff];
+    let module = spirv::Module::from_words(&words).with_validation();
+
+    // The helper doesn't exist, so the error must come from validating in the host.
+    let error = Sandbox::new("spirv-cross-sandbox-does-not-exist")
+        .compile(
+            &module,
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert!(matches!(error, ErrorCode::Validation { .. }), "{:?}", error);
+}
+
+#[test]
+fn sandbox_reports_missing_helper() {
+    let words = module_words();
+    let error = Sandbox::new("spirv-cross-sandbox-does-not-exist")
+        .compile(
+            &spirv::Module::from_words(&words),
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(
+            error,
+            ErrorCode::Io {
+                kind: std::io::ErrorKind::NotFound,
+                ..
+            }
+        ),
+        "{:?}",
+        error
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn sandbox_reports_timeout() {
+    let words = module_words();
+    let error = Sandbox::new("sleep")
+        .with_args(["10"])
+        .with_timeout(std::time::Duration::from_millis(100))
+        .compile(
+            &spirv::Module::from_words(&words),
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert_eq!(error, ErrorCode::SandboxTimedOut);
+}
+
+#[cfg(unix)]
+#[test]
+fn sandbox_reports_abort() {
+    let words = module_words();
+    let error = Sandbox::new("sh")
+        .with_args(["-c", "echo assertion failed >&2; kill -ABRT $$"])
+        .compile(
+            &spirv::Module::from_words(&words),
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert_eq!(
+        error,
+        ErrorCode::SandboxAborted(String::from("assertion failed"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn sandbox_reports_crash() {
+    let words = module_words();
+    let error = Sandbox::new("sh")
+        .with_args(["-c", "kill -SEGV $$"])
+        .compile(
+            &spirv::Module::from_words(&words),
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert!(matches!(error, ErrorCode::SandboxCrashed(_)), "{:?}", error);
+}
+
+#[cfg(unix)]
+#[test]
+fn sandbox_reports_malformed_response() {
+    let words = module_words();
+    let error = Sandbox::new("echo")
+        .with_args(["not a response"])
+        .compile(
+            &spirv::Module::from_words(&words),
+            &AnyCompilerOptions::Glsl(glsl::CompilerOptions::default()),
+        )
+        .unwrap_err();
+    assert!(matches!(error, ErrorCode::Backend(_)), "{:?}", error);
+}
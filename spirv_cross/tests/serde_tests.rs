@@ -0,0 +1,292 @@
+#![cfg(feature = "serde")]
+
+use spirv_cross::{backend, glsl, hlsl, msl, spirv, ErrorCode};
+use std::collections::BTreeSet;
+use std::io;
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+}
+
+#[test]
+fn glsl_and_hlsl_options_round_trip() {
+    let mut glsl_options = glsl::CompilerOptions::default();
+    glsl_options.version = glsl::Version::V3_10Es;
+    glsl_options.fragment.default_float_precision = glsl::Precision::Low;
+    glsl_options.entry_point = Some((String::from("main"), spirv::ExecutionModel::Fragment));
+    assert_eq!(round_trip(&glsl_options), glsl_options);
+
+    let mut hlsl_options = hlsl::CompilerOptions::default();
+    hlsl_options.shader_model = hlsl::ShaderModel::V5_1;
+    hlsl_options.vertex.invert_y = true;
+    assert_eq!(round_trip(&hlsl_options), hlsl_options);
+}
+
+#[test]
+fn msl_options_round_trip_with_overrides() {
+    let mut options = msl::CompilerOptions::default();
+    options.platform = msl::Platform::iOS;
+    options.resource_binding_overrides.insert(
+        msl::ResourceBindingLocation {
+            stage: spirv::ExecutionModel::Fragment,
+            desc_set: 1,
+            binding: 2,
+        },
+        msl::ResourceBinding {
+            buffer_id: 3,
+            texture_id: 4,
+            sampler_id: 5,
+            count: 0,
+        },
+    );
+    options.vertex_attribute_overrides.insert(
+        msl::VertexAttributeLocation(0),
+        msl::VertexAttribute {
+            buffer_id: 1,
+            format: msl::Format::Uint8,
+            built_in: Some(spirv::BuiltIn::Position),
+            vecsize: 4,
+        },
+    );
+    options.const_samplers.insert(
+        msl::SamplerLocation {
+            desc_set: 0,
+            binding: 7,
+        },
+        msl::SamplerData {
+            coord: msl::SamplerCoord::Pixel,
+            min_filter: msl::SamplerFilter::Linear,
+            mag_filter: msl::SamplerFilter::Linear,
+            mip_filter: msl::SamplerMipFilter::None,
+            s_address: msl::SamplerAddress::Repeat,
+            t_address: msl::SamplerAddress::Repeat,
+            r_address: msl::SamplerAddress::ClampToEdge,
+            compare_func: msl::SamplerCompareFunc::LessEqual,
+            border_color: msl::SamplerBorderColor::OpaqueWhite,
+            lod_clamp_min: msl::LodBase16::from(0.5),
+            lod_clamp_max: msl::LodBase16::MAX,
+            max_anisotropy: 8,
+            planes: 0,
+            resolution: msl::FormatResolution::_444,
+            chroma_filter: msl::SamplerFilter::Nearest,
+            x_chroma_offset: msl::ChromaLocation::CositedEven,
+            y_chroma_offset: msl::ChromaLocation::CositedEven,
+            swizzle: [msl::ComponentSwizzle::Identity; 4],
+            ycbcr_conversion_enable: false,
+            ycbcr_model: msl::SamplerYCbCrModelConversion::RgbIdentity,
+            ycbcr_range: msl::SamplerYCbCrRange::ItuFull,
+            bpc: 8,
+        },
+    );
+
+    assert_eq!(round_trip(&options), options);
+}
+
+#[test]
+fn options_load_from_partial_presets() {
+    let options: msl::CompilerOptions =
+        serde_json::from_str(r#"{ "version": "V2_1", "enable_argument_buffers": true }"#).unwrap();
+
+    let mut expected = msl::CompilerOptions::default();
+    expected.version = msl::Version::V2_1;
+    expected.enable_argument_buffers = true;
+    assert_eq!(options, expected);
+
+    let options: glsl::CompilerOptions =
+        serde_json::from_str(r#"{ "vertex": { "invert_y": true } }"#).unwrap();
+    assert!(options.vertex.invert_y);
+    assert_eq!(options.version, glsl::CompilerOptions::default().version);
+}
+
+#[test]
+fn reflection_round_trips() {
+    let resource = spirv::Resource {
+        id: 1,
+        type_id: 2,
+        base_type_id: 3,
+        name: String::from("uniform_buffer_object"),
+    };
+    let shader = spirv::CompiledShader {
+        source: String::from("void main() {}"),
+        entry_points: vec![spirv::EntryPoint {
+            name: String::from("main"),
+            execution_model: spirv::ExecutionModel::GlCompute,
+            work_group_size: spirv::WorkGroupSize { x: 8, y: 8, z: 1 },
+        }],
+        shader_resources: spirv::ShaderResources {
+            uniform_buffers: vec![resource.clone()],
+            storage_buffers: Vec::new(),
+            stage_inputs: Vec::new(),
+            stage_outputs: Vec::new(),
+            subpass_inputs: Vec::new(),
+            storage_images: Vec::new(),
+            sampled_images: Vec::new(),
+            atomic_counters: Vec::new(),
+            push_constant_buffers: vec![resource],
+            separate_images: Vec::new(),
+            separate_samplers: Vec::new(),
+        },
+        specialization_constants: vec![spirv::SpecializationConstant {
+            id: 4,
+            constant_id: 10,
+        }],
+    };
+    assert_eq!(round_trip(&shader), shader);
+
+    let range = spirv::BufferRange {
+        index: 0,
+        offset: 16,
+        range: 4,
+    };
+    assert_eq!(round_trip(&range), range);
+
+    let storage = spirv::StorageClass::Other(5605);
+    assert_eq!(round_trip(&storage), storage);
+
+    let ty: spirv::Type = round_trip(&spirv::Type::Image {
+        array: vec![4],
+        array_size_literal: vec![true],
+        image: spirv::ImageType {
+            type_id: 5,
+            dim: spirv::Dim::Dim2D,
+            depth: false,
+            arrayed: false,
+            ms: false,
+            sampled: 1,
+            format: spirv::ImageFormat::Rgba8,
+            access: None,
+        },
+        pointer: Some(spirv::PointerType {
+            storage: spirv::StorageClass::UniformConstant,
+            pointee_type_id: 6,
+            depth: 1,
+        }),
+    });
+    match ty {
+        spirv::Type::Image {
+            array,
+            image,
+            pointer,
+            ..
+        } => {
+            assert_eq!(array, [4]);
+            assert_eq!(image.type_id, 5);
+            assert_eq!(pointer.map(|pointer| pointer.pointee_type_id), Some(6));
+        }
+        _ => panic!("unexpected type {:?}", ty),
+    }
+}
+
+/// Numbers each variant, so that adding a variant fails to compile until it's covered below.
+fn variant(error: &ErrorCode) -> usize {
+    match error {
+        ErrorCode::Unhandled => 0,
+        ErrorCode::CompilationError(_) => 1,
+        ErrorCode::InvalidModule(_) => 2,
+        ErrorCode::UnsupportedEnumValue { .. } => 3,
+        ErrorCode::InvalidString => 4,
+        ErrorCode::NotCompiled => 5,
+        ErrorCode::Backend(_) => 6,
+        ErrorCode::InvalidHeader(_) => 7,
+        ErrorCode::Validation { .. } => 8,
+        ErrorCode::Io { .. } => 9,
+        ErrorCode::SandboxAborted(_) => 10,
+        ErrorCode::SandboxCrashed(_) => 11,
+        ErrorCode::SandboxTimedOut => 12,
+        ErrorCode::UnknownTarget(_) => 13,
+        ErrorCode::TargetMismatch { .. } => 14,
+        _ => unreachable!("unknown variant {:?}", error),
+    }
+}
+
+#[test]
+fn error_codes_round_trip() {
+    let message = || String::from("message");
+    let errors = vec![
+        ErrorCode::Unhandled,
+        ErrorCode::CompilationError(message()),
+        ErrorCode::InvalidModule(message()),
+        ErrorCode::UnsupportedEnumValue {
+            kind: "StorageClass",
+            raw: 5341,
+        },
+        ErrorCode::InvalidString,
+        ErrorCode::NotCompiled,
+        ErrorCode::Backend(message()),
+        ErrorCode::InvalidHeader(spirv::HeaderError::Truncated { words: 3 }),
+        ErrorCode::InvalidHeader(spirv::HeaderError::UnalignedLength { bytes: 7 }),
+        ErrorCode::InvalidHeader(spirv::HeaderError::InvalidMagic(0xdead_beef)),
+        ErrorCode::InvalidHeader(spirv::HeaderError::UnsupportedVersion(0x0002_0000)),
+        ErrorCode::InvalidHeader(spirv::HeaderError::InvalidBound),
+        ErrorCode::InvalidHeader(spirv::HeaderError::InvalidSchema(1)),
+        ErrorCode::Validation {
+            offset: 5,
+            error: spirv::ValidationError::ZeroWordCount,
+        },
+        ErrorCode::Validation {
+            offset: 6,
+            error: spirv::ValidationError::InstructionOutOfBounds { word_count: 9 },
+        },
+        ErrorCode::Validation {
+            offset: 7,
+            error: spirv::ValidationError::MissingOperands { opcode: 71 },
+        },
+        ErrorCode::Validation {
+            offset: 8,
+            error: spirv::ValidationError::ExtraOperands { opcode: 19 },
+        },
+        ErrorCode::Validation {
+            offset: 9,
+            error: spirv::ValidationError::UnterminatedString { opcode: 5 },
+        },
+        ErrorCode::Validation {
+            offset: 10,
+            error: spirv::ValidationError::IdOutOfBounds { id: 12, bound: 10 },
+        },
+        ErrorCode::Validation {
+            offset: 11,
+            error: spirv::ValidationError::DuplicateId(3),
+        },
+        ErrorCode::Validation {
+            offset: 12,
+            error: spirv::ValidationError::UndefinedId(4),
+        },
+        ErrorCode::Io {
+            kind: io::ErrorKind::NotFound,
+            message: message(),
+        },
+        ErrorCode::SandboxAborted(message()),
+        ErrorCode::SandboxCrashed(message()),
+        ErrorCode::SandboxTimedOut,
+        ErrorCode::UnknownTarget(String::from("spirv")),
+        ErrorCode::TargetMismatch {
+            expected: backend::TargetKind::Glsl,
+            found: backend::TargetKind::Msl,
+        },
+    ];
+
+    for error in &errors {
+        assert_eq!(&round_trip(error), error);
+    }
+
+    let covered = errors.iter().map(variant).collect::<BTreeSet<_>>();
+    assert_eq!(covered.len(), 15, "every variant is round-tripped");
+}
+
+#[test]
+fn unknown_io_error_kinds_round_trip_as_other() {
+    let error = ErrorCode::Io {
+        kind: io::ErrorKind::Deadlock,
+        message: String::from("message"),
+    };
+    assert_eq!(
+        round_trip(&error),
+        ErrorCode::Io {
+            kind: io::ErrorKind::Other,
+            message: String::from("message"),
+        }
+    );
+}
@@ -0,0 +1,47 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 14
+; Schema: 0
+               OpCapability Shader
+               OpCapability Float64
+               OpCapability Int64
+               OpCapability PhysicalStorageBufferAddresses
+               OpExtension "SPV_KHR_physical_storage_buffer"
+               OpMemoryModel PhysicalStorageBuffer64 GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpSourceExtension "GL_EXT_buffer_reference"
+               OpName %main "main"
+               OpName %Node "Node"
+               OpMemberName %Node 0 "transform"
+               OpMemberName %Node 1 "next"
+               OpName %Push "Push"
+               OpMemberName %Push 0 "root"
+               OpMemberName %Push 1 "count"
+               OpName %push "push"
+               OpMemberDecorate %Node 0 ColMajor
+               OpMemberDecorate %Node 0 Offset 0
+               OpMemberDecorate %Node 0 MatrixStride 16
+               OpMemberDecorate %Node 1 Offset 32
+               OpDecorate %Node Block
+               OpMemberDecorate %Push 0 Offset 0
+               OpMemberDecorate %Push 1 Offset 8
+               OpDecorate %Push Block
+               OpTypeForwardPointer %_ptr_PhysicalStorageBuffer_Node PhysicalStorageBuffer
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+     %double = OpTypeFloat 64
+   %v2double = OpTypeVector %double 2
+%mat2v2double = OpTypeMatrix %v2double 2
+       %Node = OpTypeStruct %mat2v2double %_ptr_PhysicalStorageBuffer_Node
+%_ptr_PhysicalStorageBuffer_Node = OpTypePointer PhysicalStorageBuffer %Node
+      %ulong = OpTypeInt 64 0
+       %Push = OpTypeStruct %_ptr_PhysicalStorageBuffer_Node %ulong
+%_ptr_PushConstant_Push = OpTypePointer PushConstant %Push
+       %push = OpVariable %_ptr_PushConstant_Push PushConstant
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,27 @@
+; SPIR-V
+; Version: 1.4
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 13
+; Schema: 0
+               OpCapability RayTracingKHR
+               OpExtension "SPV_KHR_ray_tracing"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint CallableKHR %main "main" %data
+               OpSource GLSL 460
+               OpSourceExtension "GL_EXT_ray_tracing"
+               OpName %main "main"
+               OpName %data "data"
+               OpDecorate %data Location 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v3float = OpTypeVector %float 3
+%_ptr_IncomingCallableDataKHR_v3float = OpTypePointer IncomingCallableDataKHR %v3float
+       %data = OpVariable %_ptr_IncomingCallableDataKHR_v3float IncomingCallableDataKHR
+    %float_1 = OpConstant %float 1
+         %12 = OpConstantComposite %v3float %float_1 %float_1 %float_1
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpStore %data %12
+               OpReturn
+               OpFunctionEnd
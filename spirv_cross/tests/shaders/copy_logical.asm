@@ -0,0 +1,55 @@
+; SPIR-V
+; Version: 1.4
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 24
+; Schema: 0
+               OpCapability Shader
+               OpCapability VariablePointersStorageBuffer
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main" %input %output
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Block "Block"
+               OpMemberName %Block 0 "color"
+               OpMemberName %Block 1 "weight"
+               OpName %Local "Local"
+               OpMemberName %Local 0 "color"
+               OpMemberName %Local 1 "weight"
+               OpName %input "input"
+               OpName %output "output"
+               OpName %local "local"
+               OpMemberDecorate %Block 0 Offset 0
+               OpMemberDecorate %Block 1 Offset 16
+               OpDecorate %Block Block
+               OpDecorate %input DescriptorSet 0
+               OpDecorate %input Binding 0
+               OpDecorate %output DescriptorSet 0
+               OpDecorate %output Binding 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %bool = OpTypeBool
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+      %Block = OpTypeStruct %v4float %float
+      %Local = OpTypeStruct %v4float %float
+%_ptr_StorageBuffer_Block = OpTypePointer StorageBuffer %Block
+%_ptr_Function_Local = OpTypePointer Function %Local
+      %input = OpVariable %_ptr_StorageBuffer_Block StorageBuffer
+     %output = OpVariable %_ptr_StorageBuffer_Block StorageBuffer
+    %float_0 = OpConstant %float 0
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+      %local = OpVariable %_ptr_Function_Local Function
+     %loaded = OpLoad %Block %input
+     %copied = OpCopyLogical %Local %loaded
+               OpStore %local %copied
+       %same = OpPtrEqual %bool %input %output
+   %reloaded = OpLoad %Local %local
+     %weight = OpCompositeExtract %float %reloaded 1
+    %cleared = OpSelect %float %same %float_0 %weight
+    %updated = OpCompositeInsert %Local %cleared %reloaded 1
+     %result = OpCopyLogical %Block %updated
+               OpStore %output %result
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,32 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Names "Names"
+               OpMemberName %Names 0 "fooBar"
+               OpMemberName %Names 1 "foo_bar"
+               OpMemberName %Names 2 "_pad0"
+               OpName %names "names"
+               OpMemberDecorate %Names 0 Offset 0
+               OpMemberDecorate %Names 1 Offset 4
+               OpMemberDecorate %Names 2 Offset 16
+               OpDecorate %Names Block
+               OpDecorate %names DescriptorSet 0
+               OpDecorate %names Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+      %Names = OpTypeStruct %float %float %float
+%_ptr_Uniform_Names = OpTypePointer Uniform %Names
+      %names = OpVariable %_ptr_Uniform_Names Uniform
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
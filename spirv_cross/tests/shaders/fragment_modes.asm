@@ -0,0 +1,38 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 11
+; Schema: 0
+               OpCapability Shader
+               OpCapability FragmentShaderPixelInterlockEXT
+               OpExtension "SPV_EXT_fragment_shader_interlock"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main_early "main_early"
+               OpEntryPoint Fragment %main_depth "main_depth" %gl_FragDepth
+               OpExecutionMode %main_early OriginUpperLeft
+               OpExecutionMode %main_early EarlyFragmentTests
+               OpExecutionMode %main_depth OriginUpperLeft
+               OpExecutionMode %main_depth DepthReplacing
+               OpExecutionMode %main_depth DepthGreater
+               OpExecutionMode %main_depth PixelInterlockOrderedEXT
+               OpSource GLSL 450
+               OpSourceExtension "GL_ARB_fragment_shader_interlock"
+               OpName %main_early "main_early"
+               OpName %main_depth "main_depth"
+               OpName %gl_FragDepth "gl_FragDepth"
+               OpDecorate %gl_FragDepth BuiltIn FragDepth
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+%_ptr_Output_float = OpTypePointer Output %float
+%gl_FragDepth = OpVariable %_ptr_Output_float Output
+    %float_1 = OpConstant %float 1
+ %main_early = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
+ %main_depth = OpFunction %void None %3
+          %6 = OpLabel
+               OpStore %gl_FragDepth %float_1
+               OpReturn
+               OpFunctionEnd
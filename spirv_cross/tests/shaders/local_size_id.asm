@@ -0,0 +1,27 @@
+; SPIR-V
+; Version: 1.2
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionModeId %main LocalSizeId %local_size_x %local_size_y %local_size_z
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %local_size_x "local_size_x"
+               OpName %local_size_y "local_size_y"
+               OpName %local_size_z "local_size_z"
+               OpDecorate %local_size_x SpecId 0
+               OpDecorate %local_size_y SpecId 1
+               OpDecorate %local_size_z SpecId 2
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+%local_size_x = OpSpecConstant %uint 8
+%local_size_y = OpSpecConstant %uint 8
+%local_size_z = OpSpecConstant %uint 1
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,29 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability MeshShadingNV
+               OpExtension "SPV_NV_mesh_shader"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint MeshNV %main "main" %gl_PrimitiveCountNV
+               OpExecutionMode %main LocalSize 1 1 1
+               OpExecutionMode %main OutputVertices 3
+               OpExecutionMode %main OutputPrimitivesNV 1
+               OpExecutionMode %main OutputTrianglesNV
+               OpSource GLSL 450
+               OpSourceExtension "GL_NV_mesh_shader"
+               OpName %main "main"
+               OpName %gl_PrimitiveCountNV "gl_PrimitiveCountNV"
+               OpDecorate %gl_PrimitiveCountNV BuiltIn PrimitiveCountNV
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+%_ptr_Output_uint = OpTypePointer Output %uint
+%gl_PrimitiveCountNV = OpVariable %_ptr_Output_uint Output
+     %uint_1 = OpConstant %uint 1
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpStore %gl_PrimitiveCountNV %uint_1
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,46 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 16
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Params "Params"
+               OpMemberName %Params 0 "scale"
+               OpMemberName %Params 1 "lightDir"
+               OpMemberName %Params 2 "weights"
+               OpMemberName %Params 3 "normalMatrix"
+               OpMemberName %Params 4 "size"
+               OpName %params "params"
+               OpDecorate %_arr_float_uint_2 ArrayStride 16
+               OpMemberDecorate %Params 0 Offset 0
+               OpMemberDecorate %Params 1 Offset 16
+               OpMemberDecorate %Params 2 Offset 32
+               OpMemberDecorate %Params 3 ColMajor
+               OpMemberDecorate %Params 3 Offset 64
+               OpMemberDecorate %Params 3 MatrixStride 16
+               OpMemberDecorate %Params 4 Offset 112
+               OpDecorate %Params Block
+               OpDecorate %params DescriptorSet 0
+               OpDecorate %params Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v3float = OpTypeVector %float 3
+       %uint = OpTypeInt 32 0
+     %uint_2 = OpConstant %uint 2
+%_arr_float_uint_2 = OpTypeArray %float %uint_2
+%mat3v3float = OpTypeMatrix %v3float 3
+        %int = OpTypeInt 32 1
+      %v2int = OpTypeVector %int 2
+     %Params = OpTypeStruct %float %v3float %_arr_float_uint_2 %mat3v3float %v2int
+%_ptr_Uniform_Params = OpTypePointer Uniform %Params
+     %params = OpVariable %_ptr_Uniform_Params Uniform
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
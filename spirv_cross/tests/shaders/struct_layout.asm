@@ -0,0 +1,63 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 23
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Light "Light"
+               OpMemberName %Light 0 "position"
+               OpMemberName %Light 1 "radius"
+               OpMemberName %Light 2 "basis"
+               OpName %Scene "Scene"
+               OpMemberName %Scene 0 "view"
+               OpMemberName %Scene 1 "weights"
+               OpMemberName %Scene 2 "lights"
+               OpMemberName %Scene 3 "values"
+               OpName %scene "scene"
+               OpDecorate %_arr_v4float_uint_3 ArrayStride 16
+               OpDecorate %_arr__arr_v4float_uint_3_uint_2 ArrayStride 48
+               OpMemberDecorate %Light 0 Offset 0
+               OpMemberDecorate %Light 1 Offset 12
+               OpMemberDecorate %Light 2 ColMajor
+               OpMemberDecorate %Light 2 Offset 16
+               OpMemberDecorate %Light 2 MatrixStride 8
+               OpDecorate %_arr_Light_uint_4 ArrayStride 32
+               OpDecorate %_runtimearr_float ArrayStride 4
+               OpMemberDecorate %Scene 0 RowMajor
+               OpMemberDecorate %Scene 0 Offset 0
+               OpMemberDecorate %Scene 0 MatrixStride 16
+               OpMemberDecorate %Scene 1 Offset 64
+               OpMemberDecorate %Scene 2 Offset 160
+               OpMemberDecorate %Scene 3 Offset 288
+               OpDecorate %Scene BufferBlock
+               OpDecorate %scene DescriptorSet 0
+               OpDecorate %scene Binding 0
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%mat4v4float = OpTypeMatrix %v4float 4
+       %uint = OpTypeInt 32 0
+     %uint_3 = OpConstant %uint 3
+%_arr_v4float_uint_3 = OpTypeArray %v4float %uint_3
+     %uint_2 = OpConstant %uint 2
+%_arr__arr_v4float_uint_3_uint_2 = OpTypeArray %_arr_v4float_uint_3 %uint_2
+    %v3float = OpTypeVector %float 3
+    %v2float = OpTypeVector %float 2
+%mat2v2float = OpTypeMatrix %v2float 2
+      %Light = OpTypeStruct %v3float %float %mat2v2float
+     %uint_4 = OpConstant %uint 4
+%_arr_Light_uint_4 = OpTypeArray %Light %uint_4
+%_runtimearr_float = OpTypeRuntimeArray %float
+      %Scene = OpTypeStruct %mat4v4float %_arr__arr_v4float_uint_3_uint_2 %_arr_Light_uint_4 %_runtimearr_float
+%_ptr_Uniform_Scene = OpTypePointer Uniform %Scene
+      %scene = OpVariable %_ptr_Uniform_Scene Uniform
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,59 @@
+; SPIR-V
+; Version: 1.3
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 32
+; Schema: 0
+               OpCapability Shader
+               OpCapability GroupNonUniform
+               OpCapability GroupNonUniformArithmetic
+               OpCapability GroupNonUniformQuad
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 64 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Data "Data"
+               OpMemberName %Data 0 "values"
+               OpName %data "data"
+               OpName %offsets "offsets"
+               OpDecorate %_runtimearr_uint ArrayStride 4
+               OpMemberDecorate %Data 0 Offset 0
+               OpDecorate %Data Block
+               OpDecorate %data DescriptorSet 0
+               OpDecorate %data Binding 0
+               OpDecorate %offset_x SpecId 0
+               OpDecorate %offset_y SpecId 1
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+        %int = OpTypeInt 32 1
+       %bool = OpTypeBool
+     %v2uint = OpTypeVector %uint 2
+%_runtimearr_uint = OpTypeRuntimeArray %uint
+       %Data = OpTypeStruct %_runtimearr_uint
+%_ptr_StorageBuffer_Data = OpTypePointer StorageBuffer %Data
+%_ptr_StorageBuffer_uint = OpTypePointer StorageBuffer %uint
+       %data = OpVariable %_ptr_StorageBuffer_Data StorageBuffer
+      %int_0 = OpConstant %int 0
+     %uint_0 = OpConstant %uint 0
+     %uint_1 = OpConstant %uint 1
+     %uint_3 = OpConstant %uint 3
+   %offset_x = OpSpecConstant %uint 1
+   %offset_y = OpSpecConstant %uint 2
+    %offsets = OpSpecConstantComposite %v2uint %offset_x %offset_y
+   %swizzled = OpSpecConstantOp %v2uint VectorShuffle %offsets %offsets 1 0
+     %offset = OpSpecConstantOp %uint CompositeExtract %swizzled 0
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+        %ptr = OpAccessChain %_ptr_StorageBuffer_uint %data %int_0 %offset
+      %value = OpLoad %uint %ptr
+    %swapped = OpGroupNonUniformQuadSwap %uint %uint_3 %value %uint_0
+  %broadcast = OpGroupNonUniformQuadBroadcast %uint %uint_3 %swapped %uint_1
+        %sum = OpGroupNonUniformIAdd %uint %uint_3 Reduce %broadcast
+    %is_zero = OpIEqual %bool %sum %uint_0
+   %any_zero = OpGroupNonUniformLogicalOr %bool %uint_3 Reduce %is_zero
+  %odd_zeros = OpGroupNonUniformLogicalXor %bool %uint_3 InclusiveScan %any_zero
+     %result = OpSelect %uint %odd_zeros %uint_1 %sum
+               OpStore %ptr %result
+               OpReturn
+               OpFunctionEnd
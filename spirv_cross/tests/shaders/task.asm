@@ -0,0 +1,26 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability MeshShadingNV
+               OpExtension "SPV_NV_mesh_shader"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint TaskNV %main "main" %gl_TaskCountNV
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpSourceExtension "GL_NV_mesh_shader"
+               OpName %main "main"
+               OpName %gl_TaskCountNV "gl_TaskCountNV"
+               OpDecorate %gl_TaskCountNV BuiltIn TaskCountNV
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+       %uint = OpTypeInt 32 0
+%_ptr_Output_uint = OpTypePointer Output %uint
+%gl_TaskCountNV = OpVariable %_ptr_Output_uint Output
+     %uint_1 = OpConstant %uint 1
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpStore %gl_TaskCountNV %uint_1
+               OpReturn
+               OpFunctionEnd
@@ -0,0 +1,26 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 7
+; Schema: 0
+               OpCapability Tessellation
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint TessellationControl %main_tesc "main_tesc"
+               OpEntryPoint TessellationEvaluation %main_tese "main_tese"
+               OpExecutionMode %main_tesc OutputVertices 4
+               OpExecutionMode %main_tese Quads
+               OpExecutionMode %main_tese SpacingFractionalOdd
+               OpExecutionMode %main_tese VertexOrderCw
+               OpSource GLSL 450
+               OpName %main_tesc "main_tesc"
+               OpName %main_tese "main_tese"
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+  %main_tesc = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
+  %main_tese = OpFunction %void None %3
+          %6 = OpLabel
+               OpReturn
+               OpFunctionEnd
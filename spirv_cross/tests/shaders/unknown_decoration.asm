@@ -0,0 +1,30 @@
+; SPIR-V
+; Version: 1.0
+; Generator: Khronos SPIR-V Tools Assembler; 0
+; Bound: 9
+; Schema: 0
+               OpCapability Shader
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint GLCompute %main "main"
+               OpExecutionMode %main LocalSize 1 1 1
+               OpSource GLSL 450
+               OpName %main "main"
+               OpName %Params "Params"
+               OpMemberName %Params 0 "scale"
+               OpName %params "params"
+               OpMemberDecorate %Params 0 Offset 0
+               OpMemberDecorate %Params 0 VectorComputeVariableINTEL
+               OpDecorate %Params Block
+               OpDecorate %params DescriptorSet 0
+               OpDecorate %params Binding 0
+               OpDecorate %params VectorComputeVariableINTEL
+       %void = OpTypeVoid
+          %3 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+     %Params = OpTypeStruct %float
+%_ptr_Uniform_Params = OpTypePointer Uniform %Params
+     %params = OpVariable %_ptr_Uniform_Params Uniform
+       %main = OpFunction %void None %3
+          %5 = OpLabel
+               OpReturn
+               OpFunctionEnd
@@ -1,4 +1,5 @@
 use spirv_cross::{hlsl as lang, spirv};
+use std::collections::HashMap;
 
 mod common;
 use crate::common::words_from_bytes;
@@ -18,6 +19,157 @@ fn ast_gets_multiple_entry_points() {
     assert!(entry_points.iter().any(|e| e.name == "entry_2"));
 }
 
+#[test]
+fn ast_gets_mesh_task_and_ray_tracing_entry_points() {
+    let shaders: [(&[u8], spirv::ExecutionModel); 8] = [
+        (
+            include_bytes!("shaders/task.asm.spv"),
+            spirv::ExecutionModel::TaskNv,
+        ),
+        (
+            include_bytes!("shaders/mesh.asm.spv"),
+            spirv::ExecutionModel::MeshNv,
+        ),
+        (
+            include_bytes!("shaders/raygen.asm.spv"),
+            spirv::ExecutionModel::RayGeneration,
+        ),
+        (
+            include_bytes!("shaders/intersection.asm.spv"),
+            spirv::ExecutionModel::Intersection,
+        ),
+        (
+            include_bytes!("shaders/any_hit.asm.spv"),
+            spirv::ExecutionModel::AnyHit,
+        ),
+        (
+            include_bytes!("shaders/closest_hit.asm.spv"),
+            spirv::ExecutionModel::ClosestHit,
+        ),
+        (
+            include_bytes!("shaders/miss.asm.spv"),
+            spirv::ExecutionModel::Miss,
+        ),
+        (
+            include_bytes!("shaders/callable.asm.spv"),
+            spirv::ExecutionModel::Callable,
+        ),
+    ];
+    for (bytes, execution_model) in shaders.iter() {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+        let entry_points = ast.get_entry_points().unwrap();
+        assert_eq!(entry_points.len(), 1);
+        assert_eq!(entry_points[0].name, "main");
+        assert_eq!(entry_points[0].execution_model, *execution_model);
+
+        let mut compiler_options = lang::CompilerOptions::default();
+        compiler_options.entry_point = Some((String::from("main"), *execution_model));
+        ast.set_compiler_options(&compiler_options).unwrap();
+    }
+}
+
+#[test]
+fn ast_gets_geometry_execution_modes() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/geometry.asm.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let mut expected = spirv::ExecutionModes::default();
+    expected.input_primitive = Some(spirv::InputPrimitive::Triangles);
+    expected.output_primitive = Some(spirv::OutputPrimitive::TriangleStrip);
+    expected.output_vertices = Some(3);
+    expected.invocations = Some(2);
+    assert_eq!(
+        ast.get_execution_modes("main", spirv::ExecutionModel::Geometry)
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn ast_gets_tessellation_execution_modes() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/tessellation.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let mut expected = spirv::ExecutionModes::default();
+    expected.output_vertices = Some(4);
+    assert_eq!(
+        ast.get_execution_modes("main_tesc", spirv::ExecutionModel::TessellationControl)
+            .unwrap(),
+        expected
+    );
+
+    let mut expected = spirv::ExecutionModes::default();
+    expected.input_primitive = Some(spirv::InputPrimitive::Quads);
+    expected.spacing = Some(spirv::TessellationSpacing::FractionalOdd);
+    expected.winding = Some(spirv::Winding::Clockwise);
+    assert_eq!(
+        ast.get_execution_modes("main_tese", spirv::ExecutionModel::TessellationEvaluation)
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn ast_gets_fragment_execution_modes() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/fragment_modes.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let mut expected = spirv::ExecutionModes::default();
+    expected.origin = Some(spirv::Origin::UpperLeft);
+    expected.early_fragment_tests = true;
+    assert_eq!(
+        ast.get_execution_modes("main_early", spirv::ExecutionModel::Fragment)
+            .unwrap(),
+        expected
+    );
+
+    let mut expected = spirv::ExecutionModes::default();
+    expected.origin = Some(spirv::Origin::UpperLeft);
+    expected.depth_replacing = true;
+    expected.depth = Some(spirv::DepthMode::Greater);
+    expected.interlock = Some(spirv::Interlock::PixelOrdered);
+    assert_eq!(
+        ast.get_execution_modes("main_depth", spirv::ExecutionModel::Fragment)
+            .unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn ast_gets_local_size_id() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/local_size_id.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let constant = |constant_id| {
+        ast.get_specialization_constants()
+            .unwrap()
+            .iter()
+            .find(|constant| constant.constant_id == constant_id)
+            .unwrap()
+            .id
+    };
+    let execution_modes = ast
+        .get_execution_modes("main", spirv::ExecutionModel::GlCompute)
+        .unwrap();
+    assert_eq!(
+        execution_modes.local_size_id,
+        Some(spirv::LocalSizeId {
+            x: constant(0),
+            y: constant(1),
+            z: constant(2),
+        })
+    );
+}
+
 #[test]
 fn ast_gets_shader_resources() {
     let module =
@@ -103,11 +255,13 @@ fn ast_gets_type_member_types_and_array() {
         spirv::Type::Struct {
             member_types,
             array,
-            array_size_literal
+            array_size_literal,
+            pointer,
         } => {
             assert_eq!(member_types.len(), 2);
             assert_eq!(array.len(), 0);
             assert_eq!(array_size_literal.len(), 0);
+            assert_eq!(pointer, None);
             true
         }
         _ => false,
@@ -129,11 +283,15 @@ fn ast_gets_array_dimensions() {
             assert_eq!(member_types.len(), 3);
             let is_float = match ast.get_type(member_types[2]).unwrap() {
                 spirv::Type::Float {
+                    width,
                     vecsize,
                     columns,
                     array,
-                    array_size_literal
+                    array_size_literal,
+                    pointer,
                 } => {
+                    assert_eq!(width, 32);
+                    assert_eq!(pointer, None);
                     assert_eq!(vecsize, 3);
                     assert_eq!(columns, 1);
                     assert_eq!(array.len(), 1);
@@ -153,6 +311,339 @@ fn ast_gets_array_dimensions() {
     assert!(is_struct);
 }
 
+#[test]
+fn ast_gets_pointer_types() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/buffer_reference.asm.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let push_constant_buffers = ast.get_shader_resources().unwrap().push_constant_buffers;
+    let push = &push_constant_buffers[0];
+
+    match ast.get_type(push.type_id).unwrap() {
+        spirv::Type::Struct {
+            pointer: Some(pointer),
+            ..
+        } => {
+            assert_eq!(pointer.storage, spirv::StorageClass::PushConstant);
+            assert_eq!(pointer.pointee_type_id, push.base_type_id);
+            assert_eq!(pointer.depth, 1);
+        }
+        ty => panic!("unexpected type {:?}", ty),
+    }
+
+    let member_types = match ast.get_type(push.base_type_id).unwrap() {
+        spirv::Type::Struct {
+            member_types,
+            pointer: None,
+            ..
+        } => member_types,
+        ty => panic!("unexpected type {:?}", ty),
+    };
+
+    let node_member_types = match ast.get_type(member_types[0]).unwrap() {
+        spirv::Type::Struct {
+            member_types,
+            pointer: Some(pointer),
+            ..
+        } => {
+            assert_eq!(pointer.storage, spirv::StorageClass::PhysicalStorageBuffer);
+            assert_eq!(ast.get_name(pointer.pointee_type_id).unwrap(), "Node");
+            member_types
+        }
+        ty => panic!("unexpected type {:?}", ty),
+    };
+
+    match ast.get_type(member_types[1]).unwrap() {
+        spirv::Type::UInt64 {
+            width,
+            vecsize,
+            columns,
+            pointer,
+            ..
+        } => {
+            assert_eq!((width, vecsize, columns), (64, 1, 1));
+            assert_eq!(pointer, None);
+        }
+        ty => panic!("unexpected type {:?}", ty),
+    }
+
+    match ast.get_type(node_member_types[0]).unwrap() {
+        spirv::Type::Double {
+            width,
+            vecsize,
+            columns,
+            ..
+        } => assert_eq!((width, vecsize, columns), (64, 2, 2)),
+        ty => panic!("unexpected type {:?}", ty),
+    }
+}
+
+#[test]
+fn ast_gets_struct_layout() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/struct_layout.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let storage_buffers = ast.get_shader_resources().unwrap().storage_buffers;
+    let layout = ast
+        .get_struct_layout(storage_buffers[0].base_type_id)
+        .unwrap();
+    assert_eq!(layout.size, 288);
+
+    let summary = layout
+        .members
+        .iter()
+        .map(|member| (member.name.as_str(), member.offset, member.size))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("view", 0, 64),
+            ("weights", 64, 96),
+            ("lights", 160, 128),
+            ("values", 288, 0),
+        ]
+    );
+
+    let view = &layout.members[0];
+    assert!(view.array.is_empty());
+    assert_eq!(
+        view.matrix,
+        Some(spirv::MatrixLayout {
+            stride: 16,
+            row_major: true,
+        })
+    );
+
+    let weights = &layout.members[1];
+    assert_eq!(weights.array, [3, 2]);
+    assert_eq!(weights.array_size_literal, [true, true]);
+    assert_eq!(weights.array_strides, [16, 48]);
+    assert_eq!(weights.matrix, None);
+    assert_eq!(weights.struct_layout, None);
+
+    let lights = &layout.members[2];
+    assert_eq!(lights.array, [4]);
+    assert_eq!(lights.array_strides, [32]);
+    let light = lights.struct_layout.as_ref().unwrap();
+    assert_eq!(light.size, 32);
+    let summary = light
+        .members
+        .iter()
+        .map(|member| (member.name.as_str(), member.offset, member.size))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [("position", 0, 12), ("radius", 12, 4), ("basis", 16, 16)]
+    );
+    assert_eq!(
+        light.members[2].matrix,
+        Some(spirv::MatrixLayout {
+            stride: 8,
+            row_major: false,
+        })
+    );
+
+    let values = &layout.members[3];
+    assert_eq!(values.array, [0]);
+    assert_eq!(values.array_strides, [4]);
+}
+
+#[test]
+fn ast_computes_struct_layout() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/struct_layout.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let scene = ast.get_shader_resources().unwrap().storage_buffers[0].base_type_id;
+    let declared = ast.get_struct_layout(scene).unwrap();
+    assert_eq!(
+        ast.compute_struct_layout(scene, spirv::BlockLayout::Std430)
+            .unwrap(),
+        declared
+    );
+    assert_ne!(
+        ast.compute_struct_layout(scene, spirv::BlockLayout::Std140)
+            .unwrap(),
+        declared
+    );
+
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/std140_block.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let params = ast.get_shader_resources().unwrap().uniform_buffers[0].base_type_id;
+    assert_eq!(
+        ast.compute_struct_layout(params, spirv::BlockLayout::Std140)
+            .unwrap(),
+        ast.get_struct_layout(params).unwrap()
+    );
+}
+
+fn float(vecsize: u32, columns: u32, array: &[u32]) -> spirv::Type {
+    spirv::Type::Float {
+        width: 32,
+        vecsize,
+        columns,
+        array: array.to_vec(),
+        array_size_literal: vec![true; array.len()],
+        pointer: None,
+    }
+}
+
+fn struct_type(member_types: &[u32], array: &[u32]) -> spirv::Type {
+    spirv::Type::Struct {
+        member_types: member_types.to_vec(),
+        array: array.to_vec(),
+        array_size_literal: vec![true; array.len()],
+        pointer: None,
+    }
+}
+
+/// Offset, size, array strides and matrix stride of a member.
+type MemberSummary = (u32, u32, Vec<u32>, Option<u32>);
+
+fn summarize_layout(
+    types: &HashMap<u32, spirv::Type>,
+    id: u32,
+    layout: spirv::BlockLayout,
+) -> (u32, Vec<MemberSummary>) {
+    let layout = layout.struct_layout(types, id).unwrap();
+    let members = layout
+        .members
+        .iter()
+        .map(|member| {
+            (
+                member.offset,
+                member.size,
+                member.array_strides.clone(),
+                member.matrix.map(|matrix| matrix.stride),
+            )
+        })
+        .collect();
+    (layout.size, members)
+}
+
+#[test]
+fn block_layout_applies_layout_rules() {
+    let int_vec2 = spirv::Type::Int {
+        width: 32,
+        vecsize: 2,
+        columns: 1,
+        array: Vec::new(),
+        array_size_literal: Vec::new(),
+        pointer: None,
+    };
+    let types = vec![
+        (1, float(1, 1, &[])),
+        (2, float(3, 1, &[])),
+        (3, float(1, 1, &[2])),
+        (4, float(3, 3, &[])),
+        (5, int_vec2),
+        (6, struct_type(&[1, 2, 3, 4, 5], &[])),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    assert_eq!(
+        summarize_layout(&types, 6, spirv::BlockLayout::Std140),
+        (
+            120,
+            vec![
+                (0, 4, vec![], None),
+                (16, 12, vec![], None),
+                (32, 32, vec![16], None),
+                (64, 48, vec![], Some(16)),
+                (112, 8, vec![], None),
+            ]
+        )
+    );
+    assert_eq!(
+        summarize_layout(&types, 6, spirv::BlockLayout::Std430),
+        (
+            104,
+            vec![
+                (0, 4, vec![], None),
+                (16, 12, vec![], None),
+                (28, 8, vec![4], None),
+                (48, 48, vec![], Some(16)),
+                (96, 8, vec![], None),
+            ]
+        )
+    );
+    assert_eq!(
+        summarize_layout(&types, 6, spirv::BlockLayout::Scalar),
+        (
+            68,
+            vec![
+                (0, 4, vec![], None),
+                (4, 12, vec![], None),
+                (16, 8, vec![4], None),
+                (24, 36, vec![], Some(12)),
+                (60, 8, vec![], None),
+            ]
+        )
+    );
+}
+
+#[test]
+fn block_layout_pads_nested_structs() {
+    let types = vec![
+        (1, float(1, 1, &[])),
+        (2, float(3, 1, &[])),
+        (3, struct_type(&[2], &[2])),
+        (4, struct_type(&[3, 1], &[])),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    for (layout, stride) in [
+        (spirv::BlockLayout::Std140, 16),
+        (spirv::BlockLayout::Std430, 16),
+        (spirv::BlockLayout::Scalar, 12),
+    ] {
+        assert_eq!(
+            summarize_layout(&types, 4, layout),
+            (
+                stride * 2 + 4,
+                vec![
+                    (0, stride * 2, vec![stride], None),
+                    (stride * 2, 4, vec![], None),
+                ]
+            )
+        );
+        let items = layout.struct_layout(&types, 4).unwrap().members[0]
+            .struct_layout
+            .clone()
+            .unwrap();
+        assert_eq!((items.type_id, items.size), (3, 12));
+    }
+}
+
+#[test]
+fn block_layout_rejects_specialization_constant_array_sizes() {
+    let spec_sized = spirv::Type::Float {
+        width: 32,
+        vecsize: 4,
+        columns: 1,
+        array: vec![7],
+        array_size_literal: vec![false],
+        pointer: None,
+    };
+    let types = vec![(1, spec_sized), (2, struct_type(&[1], &[]))]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    assert!(matches!(
+        spirv::BlockLayout::Std430.struct_layout(&types, 2),
+        Err(spirv_cross::ErrorCode::CompilationError(_))
+    ));
+    assert!(spirv::BlockLayout::Std430.struct_layout(&types, 1).is_err());
+}
+
 #[test]
 fn ast_gets_declared_struct_size_and_struct_member_size() {
     let module =
@@ -241,6 +732,61 @@ fn ast_sets_member_decoration() {
     );
 }
 
+#[test]
+fn ast_gets_member_decorations() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    assert_eq!(
+        ast.get_member_decorations(uniform_buffers[0].base_type_id, 0)
+            .unwrap(),
+        [
+            (spirv::Decoration::ColMajor, 1),
+            (spirv::Decoration::MatrixStride, 16),
+            (spirv::Decoration::Offset, 0),
+        ]
+    );
+    assert_eq!(
+        ast.get_member_decorations(uniform_buffers[0].base_type_id, 1)
+            .unwrap(),
+        [(spirv::Decoration::Offset, 64)]
+    );
+}
+
+#[test]
+fn ast_unsets_member_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+
+    let uniform_buffers = ast.get_shader_resources().unwrap().uniform_buffers;
+
+    ast.unset_member_decoration(
+        uniform_buffers[0].base_type_id,
+        1,
+        spirv::Decoration::Offset,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ast.get_member_decorations(uniform_buffers[0].base_type_id, 1)
+            .unwrap(),
+        []
+    );
+    assert_eq!(
+        ast.get_member_decoration(
+            uniform_buffers[0].base_type_id,
+            1,
+            spirv::Decoration::Offset
+        )
+        .unwrap(),
+        0
+    );
+}
+
 #[test]
 fn ast_gets_specialization_constants() {
     let comp = spirv::Module::from_words(words_from_bytes(include_bytes!(
@@ -332,3 +878,721 @@ fn ast_gets_active_buffer_ranges() {
         ]
     );
 }
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn ast_fails_to_parse_truncated_module() {
+    let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv"));
+    let module = spirv::Module::from_words(&words[..words.len() / 2]);
+    match spirv::Ast::<lang::Target>::parse(&module) {
+        Err(spirv_cross::ErrorCode::InvalidModule(message)) => assert!(!message.is_empty()),
+        _ => panic!("expected an invalid module error"),
+    }
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn ast_fails_to_parse_garbage_module() {
+    let words = [0xdead_beef; 32];
+    let module = spirv::Module::from_words(&words);
+    match spirv::Ast::<lang::Target>::parse(&module) {
+        Err(spirv_cross::ErrorCode::InvalidModule(message)) => assert!(!message.is_empty()),
+        _ => panic!("expected an invalid module error"),
+    }
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn ast_fails_to_parse_empty_module() {
+    let module = spirv::Module::from_words(&[]);
+    match spirv::Ast::<lang::Target>::parse(&module) {
+        Err(spirv_cross::ErrorCode::InvalidModule(message)) => assert!(!message.is_empty()),
+        _ => panic!("expected an invalid module error"),
+    }
+}
+
+#[test]
+fn ast_is_send() {
+    fn assert_send<T: Send>() {}
+    assert_send::<spirv::Ast<lang::Target>>();
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let expected = ast.compile().unwrap();
+    let compiled = std::thread::spawn(move || ast.compile().unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(compiled, expected);
+}
+
+#[test]
+fn ast_compiles_concurrently() {
+    let shaders: [&[u8]; 4] = [
+        include_bytes!("shaders/simple.vert.spv"),
+        include_bytes!("shaders/struct.vert.spv"),
+        include_bytes!("shaders/struct.frag.spv"),
+        include_bytes!("shaders/two_ubo.vert.spv"),
+    ];
+    let compile = |bytes: &[u8]| {
+        let module = spirv::Module::from_words(words_from_bytes(bytes));
+        let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+        let mut options = lang::CompilerOptions::default();
+        options.shader_model = lang::ShaderModel::V5_0;
+        ast.set_compiler_options(&options).unwrap();
+        ast.compile().unwrap()
+    };
+    let expected = shaders.iter().map(|bytes| compile(bytes)).collect::<Vec<_>>();
+
+    let threads = (0..8)
+        .map(|thread| {
+            let expected = expected.clone();
+            std::thread::spawn(move || {
+                for i in 0..50 {
+                    let index = (thread + i) % shaders.len();
+                    assert_eq!(compile(shaders[index]), expected[index]);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn ast_reports_errors_concurrently() {
+    let garbage = vec![0xdead_beef; 32];
+    let truncated = {
+        let words = words_from_bytes(include_bytes!("shaders/simple.vert.spv"));
+        words[..words.len() / 2].to_vec()
+    };
+    let error = |words: &[u32]| {
+        match spirv::Ast::<lang::Target>::parse(&spirv::Module::from_words(words)) {
+            Err(error) => error,
+            Ok(_) => panic!("expected an error"),
+        }
+    };
+    let expected = [error(&garbage), error(&truncated)];
+    assert_ne!(expected[0], expected[1]);
+
+    let modules = [garbage, truncated];
+    let threads = (0..8)
+        .map(|thread| {
+            let modules = modules.clone();
+            let expected = expected.clone();
+            std::thread::spawn(move || {
+                for i in 0..200 {
+                    let index = (thread + i) % modules.len();
+                    assert_eq!(error(&modules[index]), expected[index]);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+#[test]
+fn ast_requires_compile_for_cleansed_entry_point_name() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    assert_eq!(
+        ast.get_cleansed_entry_point_name("main", spirv::ExecutionModel::Vertex),
+        Err(spirv_cross::ErrorCode::NotCompiled)
+    );
+}
+
+#[test]
+fn ast_rejects_names_with_nul_bytes() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+    assert_eq!(
+        ast.set_name(id, "invalid\0name"),
+        Err(spirv_cross::ErrorCode::InvalidString)
+    );
+}
+
+#[test]
+fn error_code_displays_kind() {
+    use spirv_cross::ErrorCode;
+    assert_eq!(
+        ErrorCode::CompilationError(String::from("message")).to_string(),
+        "compilation error: message"
+    );
+    assert_eq!(
+        ErrorCode::UnsupportedEnumValue {
+            kind: "ExecutionModel",
+            raw: 5267,
+        }
+        .to_string(),
+        "unsupported ExecutionModel value: 5267"
+    );
+    assert_eq!(
+        ErrorCode::NotCompiled.to_string(),
+        "`compile` must be called first"
+    );
+}
+
+#[test]
+fn ast_from_parsed_module_matches_parse() {
+    use spirv_cross::{glsl, msl};
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/struct.vert.spv")));
+    let parsed = spirv::ParsedModule::parse(&module).unwrap();
+
+    let mut expected = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<lang::Target>::from_parsed_module(&parsed).unwrap();
+    assert_eq!(ast.compile().unwrap(), expected.compile().unwrap());
+
+    let mut expected = spirv::Ast::<glsl::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<glsl::Target>::from_parsed_module(&parsed).unwrap();
+    assert_eq!(ast.compile().unwrap(), expected.compile().unwrap());
+
+    let mut expected = spirv::Ast::<msl::Target>::parse(&module).unwrap();
+    let mut ast = spirv::Ast::<msl::Target>::from_parsed_module(&parsed).unwrap();
+    assert_eq!(ast.compile().unwrap(), expected.compile().unwrap());
+}
+
+#[test]
+fn asts_from_parsed_module_are_independent() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let parsed = spirv::ParsedModule::parse(&module).unwrap();
+
+    let mut ast = spirv::Ast::<lang::Target>::from_parsed_module(&parsed).unwrap();
+    let id = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+    let name = ast.get_name(id).unwrap();
+
+    let mut renamed = spirv::Ast::<lang::Target>::from_parsed_module(&parsed).unwrap();
+    renamed.set_name(id, "renamed").unwrap();
+
+    let mut ast = spirv::Ast::<lang::Target>::from_parsed_module(&parsed).unwrap();
+    assert_eq!(ast.get_name(id).unwrap(), name);
+    assert_eq!(renamed.get_name(id).unwrap(), "renamed");
+}
+
+#[test]
+fn parsed_module_is_shared_between_threads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<spirv::ParsedModule>();
+
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let parsed = std::sync::Arc::new(spirv::ParsedModule::parse(&module).unwrap());
+    let expected = spirv::Ast::<lang::Target>::parse(&module)
+        .unwrap()
+        .compile()
+        .unwrap();
+
+    let threads = (0..4)
+        .map(|_| {
+            let parsed = parsed.clone();
+            std::thread::spawn(move || {
+                spirv::Ast::<lang::Target>::from_parsed_module(&parsed)
+                    .unwrap()
+                    .compile()
+                    .unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), expected);
+    }
+}
+
+#[cfg(feature = "exceptions")]
+#[test]
+fn parsed_module_fails_to_parse_garbage_module() {
+    let words = [0xdead_beef; 32];
+    let module = spirv::Module::from_words(&words);
+    match spirv::ParsedModule::parse(&module) {
+        Err(spirv_cross::ErrorCode::InvalidModule(message)) => assert!(!message.is_empty()),
+        other => panic!("expected InvalidModule, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn ast_try_clone_keeps_renames_and_specialization_constants() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/specialization.comp.spv"
+    )));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let constant = ast.get_specialization_constants().unwrap()[0];
+    ast.set_scalar_constant(constant.id, 42).unwrap();
+    ast.set_name(constant.id, "renamed_constant").unwrap();
+
+    let mut clone = ast.try_clone().unwrap();
+    assert_eq!(clone.get_name(constant.id).unwrap(), "renamed_constant");
+    assert_eq!(clone.compile().unwrap(), ast.compile().unwrap());
+}
+
+#[test]
+fn ast_try_clone_is_independent() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+    let name = ast.get_name(id).unwrap();
+    let decoration = ast.get_decoration(id, spirv::Decoration::Binding).unwrap();
+
+    let mut clone = ast.try_clone().unwrap();
+    clone.set_name(id, "renamed").unwrap();
+    clone
+        .set_decoration(id, spirv::Decoration::Binding, decoration + 1)
+        .unwrap();
+    drop(clone);
+
+    assert_eq!(ast.get_name(id).unwrap(), name);
+    assert_eq!(
+        ast.get_decoration(id, spirv::Decoration::Binding).unwrap(),
+        decoration
+    );
+    ast.compile().unwrap();
+}
+
+#[test]
+fn module_buf_reads_header() {
+    let module = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv")).unwrap();
+    assert_eq!(
+        *module.header(),
+        spirv::Header {
+            major_version: 1,
+            minor_version: 0,
+            generator: 0x0008_0001,
+            bound: 37,
+            schema: 0,
+        }
+    );
+    assert_eq!(module.header().generator_tool(), 8);
+    assert_eq!(module.header().generator_version(), 1);
+    assert_eq!(module.words()[0], spirv::MAGIC_NUMBER);
+    assert_eq!(
+        module.words().len(),
+        include_bytes!("shaders/simple.vert.spv").len() / 4
+    );
+}
+
+#[test]
+fn module_buf_detects_byte_order() {
+    let bytes = include_bytes!("shaders/simple.vert.spv");
+    let swapped = bytes
+        .chunks_exact(4)
+        .flat_map(|word| word.iter().rev().copied())
+        .collect::<Vec<_>>();
+    let module = spirv::ModuleBuf::from_bytes(bytes).unwrap();
+    assert_eq!(spirv::ModuleBuf::from_bytes(&swapped).unwrap(), module);
+
+    let swapped_words = module
+        .words()
+        .iter()
+        .map(|word| word.swap_bytes())
+        .collect::<Vec<_>>();
+    assert_eq!(spirv::ModuleBuf::from_words(swapped_words).unwrap(), module);
+}
+
+#[test]
+fn module_buf_reads_from_reader_and_path() {
+    let expected = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv")).unwrap();
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/shaders/simple.vert.spv");
+    assert_eq!(spirv::ModuleBuf::from_path(path).unwrap(), expected);
+    assert_eq!(
+        spirv::ModuleBuf::from_reader(std::fs::File::open(path).unwrap()).unwrap(),
+        expected
+    );
+
+    let mut ast = spirv::Ast::<lang::Target>::parse(&expected.as_module()).unwrap();
+    ast.compile().unwrap();
+}
+
+#[test]
+fn module_buf_reports_missing_file() {
+    match spirv::ModuleBuf::from_path("tests/shaders/missing.spv") {
+        Err(spirv_cross::ErrorCode::Io { kind, .. }) => {
+            assert_eq!(kind, std::io::ErrorKind::NotFound)
+        }
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+}
+
+#[test]
+fn module_buf_rejects_invalid_headers() {
+    use spirv::HeaderError;
+    use spirv_cross::ErrorCode;
+
+    let words = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words();
+    let with_word = |index: usize, value: u32| {
+        let mut words = words.to_vec();
+        words[index] = value;
+        spirv::ModuleBuf::from_words(words)
+    };
+
+    assert_eq!(
+        spirv::ModuleBuf::from_words(words[..3].to_vec()),
+        Err(ErrorCode::InvalidHeader(HeaderError::Truncated {
+            words: 3
+        }))
+    );
+    assert_eq!(
+        spirv::ModuleBuf::from_bytes(&[0x03, 0x02, 0x23, 0x07, 0x00]),
+        Err(ErrorCode::InvalidHeader(HeaderError::UnalignedLength {
+            bytes: 5
+        }))
+    );
+    assert_eq!(
+        with_word(0, 0xdead_beef),
+        Err(ErrorCode::InvalidHeader(HeaderError::InvalidMagic(
+            0xdead_beef
+        )))
+    );
+    assert_eq!(
+        with_word(1, 0x0002_0000),
+        Err(ErrorCode::InvalidHeader(HeaderError::UnsupportedVersion(
+            0x0002_0000
+        )))
+    );
+    assert_eq!(
+        with_word(1, 0x0001_0001),
+        Err(ErrorCode::InvalidHeader(HeaderError::UnsupportedVersion(
+            0x0001_0001
+        )))
+    );
+    assert_eq!(
+        with_word(3, 0),
+        Err(ErrorCode::InvalidHeader(HeaderError::InvalidBound))
+    );
+    assert_eq!(
+        with_word(4, 1),
+        Err(ErrorCode::InvalidHeader(HeaderError::InvalidSchema(1)))
+    );
+    assert!(with_word(2, 0).is_ok());
+}
+
+#[test]
+fn module_validates_test_shaders() {
+    let shaders: [&[u8]; 30] = [
+        include_bytes!("shaders/array.vert.spv"),
+        include_bytes!("shaders/const_array.vert.spv"),
+        include_bytes!("shaders/initialization.vert.spv"),
+        include_bytes!("shaders/multiple_entry_points.cl.spv"),
+        include_bytes!("shaders/rasterize_disabled.vert.spv"),
+        include_bytes!("shaders/sampler.frag.spv"),
+        include_bytes!("shaders/simple.vert.spv"),
+        include_bytes!("shaders/specialization.comp.spv"),
+        include_bytes!("shaders/struct.frag.spv"),
+        include_bytes!("shaders/struct.vert.spv"),
+        include_bytes!("shaders/two_ubo.vert.spv"),
+        include_bytes!("shaders/vs_and_fs.asm.spv"),
+        include_bytes!("shaders/workgroup.comp.spv"),
+        include_bytes!("shaders/task.asm.spv"),
+        include_bytes!("shaders/mesh.asm.spv"),
+        include_bytes!("shaders/raygen.asm.spv"),
+        include_bytes!("shaders/intersection.asm.spv"),
+        include_bytes!("shaders/any_hit.asm.spv"),
+        include_bytes!("shaders/closest_hit.asm.spv"),
+        include_bytes!("shaders/miss.asm.spv"),
+        include_bytes!("shaders/callable.asm.spv"),
+        include_bytes!("shaders/geometry.asm.spv"),
+        include_bytes!("shaders/tessellation.asm.spv"),
+        include_bytes!("shaders/fragment_modes.asm.spv"),
+        include_bytes!("shaders/local_size_id.asm.spv"),
+        include_bytes!("shaders/buffer_reference.asm.spv"),
+        include_bytes!("shaders/struct_layout.asm.spv"),
+        include_bytes!("shaders/std140_block.asm.spv"),
+        include_bytes!("shaders/subgroup_quad.asm.spv"),
+        include_bytes!("shaders/copy_logical.asm.spv"),
+    ];
+    for bytes in shaders.iter() {
+        let module = spirv::ModuleBuf::from_bytes(bytes).unwrap();
+        module.as_module().validate().unwrap();
+    }
+}
+
+#[test]
+fn module_validation_rejects_malformed_instructions() {
+    use spirv::ValidationError;
+    use spirv_cross::ErrorCode;
+
+    let words = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words();
+    let end = words.len();
+    let validate = |extra: &[u32]| {
+        let mut words = words.to_vec();
+        words.extend_from_slice(extra);
+        spirv::Module::from_words(&words).validate()
+    };
+    let op_name = |word_count: u32| (word_count << 16) | 5;
+
+    assert_eq!(
+        validate(&[0]),
+        Err(ErrorCode::Validation {
+            offset: end,
+            error: ValidationError::ZeroWordCount,
+        })
+    );
+    assert_eq!(
+        validate(&[op_name(4), 1]),
+        Err(ErrorCode::Validation {
+            offset: end,
+            error: ValidationError::InstructionOutOfBounds { word_count: 4 },
+        })
+    );
+    assert_eq!(
+        validate(&[op_name(2), 1]),
+        Err(ErrorCode::Validation {
+            offset: end,
+            error: ValidationError::MissingOperands { opcode: 5 },
+        })
+    );
+    assert_eq!(validate(&[op_name(3), 1, 0x0061_6161]), Ok(()));
+    assert_eq!(
+        validate(&[op_name(3), 1, 0x6161_6161]),
+        Err(ErrorCode::Validation {
+            offset: end,
+            error: ValidationError::UnterminatedString { opcode: 5 },
+        })
+    );
+    assert_eq!(
+        validate(&[(2 << 16) | 248, 1]),
+        Err(ErrorCode::Validation {
+            offset: end + 1,
+            error: ValidationError::DuplicateId(1),
+        })
+    );
+    assert_eq!(
+        validate(&[(3 << 16) | 249, 1, 2]),
+        Err(ErrorCode::Validation {
+            offset: end,
+            error: ValidationError::ExtraOperands { opcode: 249 },
+        })
+    );
+    assert_eq!(
+        validate(&[op_name(3), 1000, 0]),
+        Err(ErrorCode::Validation {
+            offset: end + 1,
+            error: ValidationError::IdOutOfBounds {
+                id: 1000,
+                bound: 37,
+            },
+        })
+    );
+}
+
+#[test]
+fn module_validation_accepts_newer_and_unknown_instructions() {
+    // Group operation literals, OpGroupNonUniformQuadSwap and OpSpecConstantOp with literal
+    // operands in SPIR-V 1.3, then OpCopyLogical and OpPtrEqual in SPIR-V 1.4.
+    for bytes in [
+        &include_bytes!("shaders/subgroup_quad.asm.spv")[..],
+        &include_bytes!("shaders/copy_logical.asm.spv")[..],
+    ] {
+        let module = spirv::ModuleBuf::from_bytes(bytes).unwrap();
+        let header = module.header();
+        assert!((header.major_version, header.minor_version) >= (1, 3));
+        module.as_module().validate().unwrap();
+    }
+
+    let mut words = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words();
+    words[3] = 100;
+    // An OpExtInst from `GLSL.std.450` (%1) with literal operands, which are not checked.
+    words.extend_from_slice(&[(7 << 16) | 12, 6, 98, 1, 31, 0, 4000]);
+    // An unknown opcode which defines %99, followed by a reference to it.
+    words.extend_from_slice(&[(4 << 16) | 0xfff0, 6, 99, 0]);
+    words.extend_from_slice(&[(3 << 16) | 5, 99, 0]);
+    assert_eq!(spirv::Module::from_words(&words).validate(), Ok(()));
+}
+
+#[test]
+fn module_validation_rejects_undefined_ids() {
+    use spirv_cross::ErrorCode;
+
+    let mut words = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words();
+    let end = words.len();
+    // Raise the bound, then name an ID below it which is never defined.
+    words[3] = 100;
+    words.extend_from_slice(&[(3 << 16) | 5, 99, 0]);
+    assert_eq!(
+        spirv::Module::from_words(&words).validate(),
+        Err(ErrorCode::Validation {
+            offset: end + 1,
+            error: spirv::ValidationError::UndefinedId(99),
+        })
+    );
+}
+
+#[test]
+fn ast_parse_runs_requested_validation() {
+    let mut words = spirv::ModuleBuf::from_bytes(include_bytes!("shaders/simple.vert.spv"))
+        .unwrap()
+        .into_words();
+    words.push(0);
+    let module = spirv::Module::from_words(&words).with_validation();
+    match spirv::Ast::<lang::Target>::parse(&module) {
+        Err(spirv_cross::ErrorCode::Validation { offset, error }) => {
+            assert_eq!(offset, words.len() - 1);
+            assert_eq!(error, spirv::ValidationError::ZeroWordCount);
+        }
+        _ => panic!("expected a validation error"),
+    }
+}
+
+#[test]
+fn decoration_round_trips_through_raw_value() {
+    let decorations = (0..6000)
+        .filter_map(|raw| spirv::Decoration::from_raw(raw).ok())
+        .collect::<Vec<_>>();
+    assert_eq!(decorations.len(), 78);
+    for decoration in decorations {
+        assert_eq!(
+            spirv::Decoration::from_raw(decoration as u32),
+            Ok(decoration)
+        );
+    }
+
+    assert_eq!(spirv::Decoration::NonUniform as u32, 5300);
+    assert_eq!(spirv::Decoration::UserSemantic as u32, 5635);
+    assert_eq!(
+        spirv::Decoration::from_raw(12),
+        Err(spirv_cross::ErrorCode::UnsupportedEnumValue {
+            kind: "Decoration",
+            raw: 12
+        })
+    );
+}
+
+#[test]
+fn ast_sets_and_gets_every_decoration() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+
+    for decoration in (0..6000).filter_map(|raw| spirv::Decoration::from_raw(raw).ok()) {
+        if ast.get_decoration(id, decoration).unwrap() != 0 {
+            continue;
+        }
+        ast.set_decoration(id, decoration, 1).unwrap();
+        assert_eq!(
+            ast.get_decoration(id, decoration).unwrap(),
+            1,
+            "{:?}",
+            decoration
+        );
+        ast.unset_decoration(id, decoration).unwrap();
+        assert_eq!(
+            ast.get_decoration(id, decoration).unwrap(),
+            0,
+            "{:?}",
+            decoration
+        );
+    }
+}
+
+#[test]
+fn ast_sets_and_gets_decoration_strings() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast.get_shader_resources().unwrap().stage_inputs[0].id;
+
+    assert_eq!(
+        ast.get_decoration_string(id, spirv::Decoration::UserSemantic)
+            .unwrap(),
+        ""
+    );
+    ast.set_decoration_string(id, spirv::Decoration::UserSemantic, "POSITION")
+        .unwrap();
+    assert_eq!(
+        ast.get_decoration_string(id, spirv::Decoration::UserSemantic)
+            .unwrap(),
+        "POSITION"
+    );
+}
+
+#[test]
+fn ast_gets_decorations() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast.get_shader_resources().unwrap().uniform_buffers[0].id;
+
+    assert_eq!(
+        ast.get_decorations(id).unwrap(),
+        [(spirv::Decoration::DescriptorSet, 0)]
+    );
+    assert!(ast
+        .has_decoration(id, spirv::Decoration::DescriptorSet)
+        .unwrap());
+    assert!(!ast.has_decoration(id, spirv::Decoration::Binding).unwrap());
+
+    ast.set_decoration(id, spirv::Decoration::Binding, 0)
+        .unwrap();
+    assert!(ast.has_decoration(id, spirv::Decoration::Binding).unwrap());
+    assert_eq!(
+        ast.get_decorations(id).unwrap(),
+        [
+            (spirv::Decoration::Binding, 0),
+            (spirv::Decoration::DescriptorSet, 0),
+        ]
+    );
+}
+
+#[test]
+fn ast_lists_decoration_strings_with_an_argument_of_1() {
+    let module =
+        spirv::Module::from_words(words_from_bytes(include_bytes!("shaders/simple.vert.spv")));
+    let mut ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let id = ast
+        .get_shader_resources()
+        .unwrap()
+        .stage_inputs
+        .into_iter()
+        .find(|input| input.name == "a_position")
+        .unwrap()
+        .id;
+
+    ast.set_decoration_string(id, spirv::Decoration::UserSemantic, "POSITION")
+        .unwrap();
+    assert_eq!(
+        ast.get_decorations(id).unwrap(),
+        [
+            (spirv::Decoration::Location, 0),
+            (spirv::Decoration::UserSemantic, 1),
+        ]
+    );
+}
+
+#[test]
+fn ast_skips_unknown_decorations() {
+    let module = spirv::Module::from_words(words_from_bytes(include_bytes!(
+        "shaders/unknown_decoration.asm.spv"
+    )));
+    let ast = spirv::Ast::<lang::Target>::parse(&module).unwrap();
+    let uniform_buffer = &ast.get_shader_resources().unwrap().uniform_buffers[0];
+
+    assert_eq!(
+        ast.get_decorations(uniform_buffer.id).unwrap(),
+        [
+            (spirv::Decoration::Binding, 0),
+            (spirv::Decoration::DescriptorSet, 0),
+        ]
+    );
+    assert_eq!(
+        ast.get_member_decorations(uniform_buffer.base_type_id, 0)
+            .unwrap(),
+        [(spirv::Decoration::Offset, 0)]
+    );
+}
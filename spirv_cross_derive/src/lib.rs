@@ -0,0 +1,106 @@
+//! `#[derive(ShaderBlock)]` for `spirv_cross`. Enable the `derive` feature of `spirv_cross` and
+//! use it as `spirv_cross::spirv::ShaderBlock` instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Type};
+
+/// Implements `spirv_cross::spirv::ShaderBlock` for a struct with named fields.
+///
+/// Fields whose type, or array element type, is a struct which also implements `ShaderBlock`
+/// can be marked `#[shader_block(nested)]` so its fields are checked too.
+#[proc_macro_derive(ShaderBlock, attributes(shader_block))]
+pub fn derive_shader_block(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "ShaderBlock can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "ShaderBlock can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut descriptions = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().expect("named field");
+        let name = ident.to_string();
+        let ty = &field.ty;
+
+        let mut nested = false;
+        for attr in &field.attrs {
+            if attr.path().is_ident("shader_block") {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("nested") {
+                        nested = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unknown shader_block attribute"))
+                    }
+                })?;
+            }
+        }
+
+        // Strides are listed innermost first, as in `spirv::StructMemberLayout`.
+        let mut strides = Vec::new();
+        let mut element = ty;
+        while let Some(inner) = array_element(element) {
+            strides.push(quote!(::core::mem::size_of::<#inner>()));
+            element = inner;
+        }
+        strides.reverse();
+
+        let nested = if nested {
+            quote!(::core::option::Option::Some(
+                <#element as ::spirv_cross::spirv::ShaderBlock>::fields
+            ))
+        } else {
+            quote!(::core::option::Option::None)
+        };
+
+        descriptions.push(quote! {
+            ::spirv_cross::spirv::BlockField {
+                name: #name,
+                offset: ::core::mem::offset_of!(Self, #ident),
+                size: ::core::mem::size_of::<#ty>(),
+                array_strides: ::std::vec![#(#strides),*],
+                fields: #nested,
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::spirv_cross::spirv::ShaderBlock for #ident #ty_generics #where_clause {
+            fn fields() -> ::std::vec::Vec<::spirv_cross::spirv::BlockField> {
+                ::std::vec![#(#descriptions),*]
+            }
+        }
+    })
+}
+
+fn array_element(ty: &Type) -> Option<&Type> {
+    match ty {
+        Type::Array(array) => Some(&array.elem),
+        Type::Group(group) => array_element(&group.elem),
+        Type::Paren(paren) => array_element(&paren.elem),
+        _ => None,
+    }
+}